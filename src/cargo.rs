@@ -0,0 +1,133 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+
+use crate::write_file;
+
+/// Reads `<project>/Cargo.toml`, hands it to `edit` and writes it back.
+/// `toml_edit` keeps the formatting and comments `cargo new` produced.
+pub fn edit_manifest(project: &Path, edit: impl FnOnce(&mut DocumentMut)) -> Result<()> {
+    let path = project.join("Cargo.toml");
+    let text = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Failed to read file {:?}: {}", path, e))?;
+    let mut doc: DocumentMut = text
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse {:?}: {}", path, e))?;
+    edit(&mut doc);
+    write_file(&path, &doc.to_string())
+}
+
+/// True if `<project>/Cargo.toml` declares `[lib] proc-macro = true`.
+pub fn is_proc_macro(project: &Path) -> bool {
+    fs::read_to_string(project.join("Cargo.toml"))
+        .ok()
+        .and_then(|text| text.parse::<DocumentMut>().ok())
+        .and_then(|doc| doc.get("lib")?.get("proc-macro")?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Adds `name = "version"` to `[dependencies]`, creating the table if needed.
+/// With `features` the entry becomes `name = { version, features = [...] }`.
+pub fn add_dependency(doc: &mut DocumentMut, name: &str, version: &str, features: &[&str]) {
+    let entry = if features.is_empty() {
+        value(version)
+    } else {
+        let mut table = InlineTable::new();
+        table.insert("version", version.into());
+        table.insert("features", Value::Array(features.iter().copied().collect()));
+        value(table)
+    };
+    table(doc, "dependencies")[name] = entry;
+}
+
+/// Extra features for one template dependency, from `--rust-features name=a,b`.
+#[derive(Clone)]
+pub struct CrateFeatures {
+    pub name: String,
+    pub features: Vec<String>,
+}
+
+/// clap value parser for `--rust-features`.
+pub fn parse_crate_features(arg: &str) -> Result<CrateFeatures, String> {
+    let (name, features) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected CRATE=FEATURE[,FEATURE...], got '{}'", arg))?;
+    let features: Vec<String> = features
+        .split(',')
+        .map(str::trim)
+        .filter(|feature| !feature.is_empty())
+        .map(str::to_string)
+        .collect();
+    if name.trim().is_empty() || features.is_empty() {
+        return Err(format!("expected CRATE=FEATURE[,FEATURE...], got '{}'", arg));
+    }
+    Ok(CrateFeatures {
+        name: name.trim().to_string(),
+        features,
+    })
+}
+
+/// Returns the top-level `[name]` table, creating an empty one if needed.
+pub fn table<'a>(doc: &'a mut DocumentMut, name: &str) -> &'a mut Table {
+    doc.entry(name)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .unwrap_or_else(|| panic!("[{}] is a table", name))
+}
+
+/// Checks `name` against the rules cargo applies to package names.
+pub fn validate_crate_name(name: &str) -> Result<()> {
+    validate_target_name("crate", name)
+}
+
+/// Binary names that would collide with directories cargo creates in `target/`.
+const FORBIDDEN_BIN_NAMES: &[&str] = &["build", "deps", "examples", "incremental"];
+
+/// Checks `name` against the rules cargo applies to `[[bin]]` target names.
+pub fn validate_bin_name(name: &str) -> Result<()> {
+    validate_target_name("binary", name)?;
+    if FORBIDDEN_BIN_NAMES.contains(&name) {
+        return Err(anyhow!(
+            "Invalid binary name '{}': cargo reserves it for its build directories.",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Adds a `[[bin]]` target called `name` built from `path`.
+pub fn add_bin_target(doc: &mut DocumentMut, name: &str, path: &str) {
+    let mut bin = Table::new();
+    bin["name"] = value(name);
+    bin["path"] = value(path);
+    let bins = doc
+        .entry("bin")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .expect("[[bin]] is an array of tables");
+    bins.push(bin);
+}
+
+fn validate_target_name(kind: &str, name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return Err(anyhow!("The {} name must not be empty.", kind));
+    };
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        return Err(anyhow!(
+            "Invalid {} name '{}': it must start with a letter or underscore.",
+            kind,
+            name
+        ));
+    }
+    if let Some(bad) = chars.find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_')) {
+        return Err(anyhow!(
+            "Invalid {} name '{}': '{}' is not allowed, use letters, digits, '-' or '_'.",
+            kind,
+            name,
+            bad
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::proxy;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+const MAX_REDIRECTS: u32 = 5;
+
+/// Downloads `url` to `dest`, following redirects, with a progress bar on
+/// stderr. When `expected_sha256` is given the body must hash to it, or
+/// nothing is written. The body lands in `<dest>.part` first, so `dest` never
+/// holds a truncated or unverified file.
+pub fn download(url: &str, dest: &Path, expected_sha256: Option<&str>) -> Result<()> {
+    let agent = proxy::agent_for(url)?
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .redirects(MAX_REDIRECTS)
+        .build();
+    let response = match agent.get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, response)) => {
+            return Err(anyhow!(
+                "Failed to download {}: server answered {} {}",
+                url,
+                code,
+                response.status_text()
+            ));
+        }
+        Err(e) => return Err(anyhow!("Failed to download {}: {}", url, e)),
+    };
+    if response.status() != 200 {
+        return Err(anyhow!(
+            "Failed to download {}: server answered {} {}",
+            url,
+            response.status(),
+            response.status_text()
+        ));
+    }
+
+    let progress = match response.header("Content-Length").and_then(|len| len.parse().ok()) {
+        Some(len) => ProgressBar::new(len).with_style(
+            ProgressStyle::with_template("{bar:40} {bytes}/{total_bytes} {msg}")
+                .expect("valid progress template"),
+        ),
+        None => ProgressBar::new_spinner(),
+    };
+    progress.set_message(url.to_string());
+
+    let file_name = dest
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid download destination {:?}", dest))?;
+    let partial = dest.with_file_name(format!("{}.part", file_name.to_string_lossy()));
+    let result = (|| -> Result<String> {
+        let mut file = File::create(&partial)
+            .map_err(|e| anyhow!("Failed to create file {:?}: {}", partial, e))?;
+        let mut reader = response.into_reader();
+        let mut hasher = Sha256::new();
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| anyhow!("Failed to download {}: {}", url, e))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            file.write_all(&buffer[..read])
+                .map_err(|e| anyhow!("Failed to write file {:?}: {}", partial, e))?;
+            progress.inc(read as u64);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    })();
+    progress.finish_and_clear();
+
+    let verified = result.and_then(|actual| match expected_sha256 {
+        Some(expected) if !actual.eq_ignore_ascii_case(expected.trim()) => Err(anyhow!(
+            "Checksum mismatch for {}: expected sha256 {}, got {}",
+            url,
+            expected.trim(),
+            actual
+        )),
+        _ => Ok(()),
+    });
+    if let Err(e) = verified {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, dest).map_err(|e| anyhow!("Failed to create file {:?}: {}", dest, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::thread;
+
+    const BODY: &str = "hello";
+    const BODY_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    /// Serves canned responses on localhost until the test process exits and
+    /// returns its base URL.
+    fn mock_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind a local port");
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                // Drain the headers so the client sees a clean close.
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+                    header.clear();
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let response = match path {
+                    "/file" => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        BODY.len(),
+                        BODY
+                    ),
+                    "/moved" => "HTTP/1.1 302 Found\r\nLocation: /file\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string(),
+                    _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn writes_the_body_of_a_200_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("file.txt");
+        download(&format!("{}/file", mock_server()), &dest, Some(BODY_SHA256)).unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), BODY);
+        assert!(!dest.with_file_name("file.txt.part").exists());
+    }
+
+    #[test]
+    fn reports_a_non_200_status_and_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("file.txt");
+        let error = download(&format!("{}/missing", mock_server()), &dest, None).unwrap_err();
+        assert!(error.to_string().contains("404"), "{}", error);
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn follows_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("file.txt");
+        download(&format!("{}/moved", mock_server()), &dest, None).unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), BODY);
+    }
+
+    #[test]
+    fn rejects_a_checksum_mismatch_and_leaves_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("file.txt");
+        let wrong = "0".repeat(64);
+        let error = download(&format!("{}/file", mock_server()), &dest, Some(&wrong)).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"), "{}", error);
+        assert!(!dest.exists());
+        assert!(!dest.with_file_name("file.txt.part").exists());
+    }
+
+    #[test]
+    fn accepts_an_uppercase_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("file.txt");
+        let expected = BODY_SHA256.to_ascii_uppercase();
+        download(&format!("{}/file", mock_server()), &dest, Some(&expected)).unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), BODY);
+    }
+}
@@ -0,0 +1,232 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Indentation an `.editorconfig` asks for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Tabs,
+    Spaces(usize),
+}
+
+/// Resolves the indentation `.editorconfig` files declare for `path`,
+/// searching its directory and every parent until one sets `root = true`.
+/// Closer files win over farther ones and later sections over earlier ones,
+/// as in the EditorConfig spec. Only `*`, `**`, `?` and `{a,b}` globs are
+/// understood; anything else simply does not match. YAML files and Makefiles,
+/// where indentation is syntax, never get one.
+pub fn indent_for(path: &Path) -> Option<Indent> {
+    if indentation_is_syntax(path) {
+        return None;
+    }
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().ok()?.join(path)
+    };
+    let mut configs = Vec::new();
+    for dir in path.ancestors().skip(1) {
+        let Ok(text) = fs::read_to_string(dir.join(".editorconfig")) else {
+            continue;
+        };
+        let root = parse(&text).0;
+        configs.push((dir.to_path_buf(), text));
+        if root {
+            break;
+        }
+    }
+
+    let mut style = None;
+    let mut size = None;
+    for (dir, text) in configs.iter().rev() {
+        let relative = relative_path(&path, dir);
+        for (pattern, properties) in parse(text).1 {
+            if !section_matches(&pattern, &relative) {
+                continue;
+            }
+            for (key, value) in properties {
+                match key.as_str() {
+                    "indent_style" => style = Some(value),
+                    "indent_size" => size = Some(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+    match style.as_deref() {
+        Some("tab") => Some(Indent::Tabs),
+        Some("space") => size?.parse().ok().filter(|&n| n > 0).map(Indent::Spaces),
+        _ => None,
+    }
+}
+
+/// Re-indents `content` to `indent`. The width of one level in the input is
+/// taken from its least indented line, so templates written with two or four
+/// spaces (or tabs) convert alike; spaces beyond a whole level are kept.
+pub fn reindent(content: &str, indent: Indent) -> String {
+    let unit = content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('\t'))
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|&spaces| spaces > 0)
+        .min();
+    let mut result = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_start_matches([' ', '\t']);
+        let leading = &line[..line.len() - body.len()];
+        if body.trim().is_empty() || leading.is_empty() {
+            result.push_str(line);
+            continue;
+        }
+        let tabs = leading.chars().take_while(|&c| c == '\t').count();
+        let spaces = leading[tabs..].chars().take_while(|&c| c == ' ').count();
+        if tabs + spaces != leading.len() {
+            // Mixed indentation is left for the user to sort out.
+            result.push_str(line);
+            continue;
+        }
+        let (levels, extra) = match unit {
+            Some(unit) => (tabs + spaces / unit, spaces % unit),
+            None => (tabs, spaces),
+        };
+        match indent {
+            Indent::Tabs => result.push_str(&"\t".repeat(levels)),
+            Indent::Spaces(width) => result.push_str(&" ".repeat(levels * width)),
+        }
+        result.push_str(&" ".repeat(extra));
+        result.push_str(body);
+    }
+    result
+}
+
+fn indentation_is_syntax(path: &Path) -> bool {
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    matches!(name, "Makefile" | "makefile" | "GNUmakefile")
+        || matches!(extension.to_ascii_lowercase().as_str(), "yaml" | "yml" | "mk")
+}
+
+type Section = (String, Vec<(String, String)>);
+
+/// Splits an `.editorconfig` into its `root` flag and its sections.
+fn parse(text: &str) -> (bool, Vec<Section>) {
+    let mut root = false;
+    let mut sections: Vec<Section> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(pattern) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            sections.push((pattern.to_string(), Vec::new()));
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim().to_ascii_lowercase();
+        match sections.last_mut() {
+            Some((_, properties)) => properties.push((key, value)),
+            None if key == "root" => root = value == "true",
+            None => {}
+        }
+    }
+    (root, sections)
+}
+
+fn relative_path(path: &Path, dir: &Path) -> String {
+    path.strip_prefix(dir)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Patterns without a `/` match the file name at any depth; others match
+/// the path relative to the `.editorconfig`.
+fn section_matches(pattern: &str, relative: &str) -> bool {
+    if pattern.contains('/') {
+        glob(pattern.trim_start_matches('/').as_bytes(), relative.as_bytes())
+    } else {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        glob(pattern.as_bytes(), name.as_bytes())
+    }
+}
+
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob(rest, &text[i..])),
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob(rest, &text[i..])),
+        [b'?', rest @ ..] => matches!(text, [c, tail @ ..] if *c != b'/' && glob(rest, tail)),
+        [b'{', rest @ ..] => {
+            let Some(close) = rest.iter().position(|&c| c == b'}') else {
+                return false;
+            };
+            let after = &rest[close + 1..];
+            rest[..close].split(|&c| c == b',').any(|alternative| {
+                let mut candidate = alternative.to_vec();
+                candidate.extend_from_slice(after);
+                glob(&candidate, text)
+            })
+        }
+        [c, rest @ ..] => matches!(text, [t, tail @ ..] if t == c && glob(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_section_globs() {
+        for (pattern, relative, expected) in [
+            ("*.{py,rs}", "main.py", true),
+            ("*.{py,rs}", "src/lib.rs", true),
+            ("*.{py,rs}", "setup.cfg", false),
+            ("{Makefile,*.mk}", "rules.mk", true),
+            ("src/*.rs", "src/main.rs", true),
+            ("src/*.rs", "src/bin/tool.rs", false),
+            ("src/**.rs", "src/bin/tool.rs", true),
+            ("/src/*.rs", "src/main.rs", true),
+            ("?.py", "a.py", true),
+            ("?.py", "ab.py", false),
+        ] {
+            assert_eq!(section_matches(pattern, relative), expected, "[{}] {}", pattern, relative);
+        }
+    }
+
+    #[test]
+    fn closer_files_and_later_sections_win() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path();
+        let nested = dir.join("project").join("src");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join(".editorconfig"), "root = true\n[*]\nindent_style = tab\n").unwrap();
+        fs::write(
+            dir.join("project").join(".editorconfig"),
+            "[*.py]\nindent_style = space\nindent_size = 2\n[src/*.py]\nindent_size = 4\n",
+        )
+        .unwrap();
+
+        let indent = |path: &Path| indent_for(&dir.join(path));
+        assert!(indent(Path::new("project/main.rs")) == Some(Indent::Tabs));
+        assert!(indent(Path::new("project/app.py")) == Some(Indent::Spaces(2)));
+        assert!(indent(Path::new("project/src/app.py")) == Some(Indent::Spaces(4)));
+        assert!(indent(Path::new("project/ci.yml")).is_none());
+    }
+
+    #[test]
+    fn reindents_between_tabs_and_spaces() {
+        let four = "def f():\n    if x:\n        return 1\n\n    return 2\n";
+        let tabs = "def f():\n\tif x:\n\t\treturn 1\n\n\treturn 2\n";
+        assert_eq!(reindent(four, Indent::Tabs), tabs);
+        assert_eq!(reindent(tabs, Indent::Spaces(4)), four);
+        assert_eq!(reindent(four, Indent::Spaces(2)), "def f():\n  if x:\n    return 1\n\n  return 2\n");
+        // Spaces beyond a whole level are continuation alignment and stay.
+        assert_eq!(reindent("f(\n  a,\n   b)\n", Indent::Tabs), "f(\n\ta,\n\t b)\n");
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{anyhow, Result};
+use std::env;
+use std::path::PathBuf;
+
+/// Expands a leading `~` and `$VAR`, `${VAR}` or `${VAR:-default}` references
+/// in a path given on the command line. As in the shell, `${VAR:-default}`
+/// also uses the default when `VAR` is empty. Referencing an undefined variable is
+/// an error unless a default is given; a `$` that does not start a reference
+/// is kept as it is.
+pub fn expand_path(raw: &str) -> Result<PathBuf> {
+    expand_with(raw, |name| env::var(name).ok())
+}
+
+fn expand_with(raw: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    let mut expanded = String::new();
+    let mut rest = raw;
+    if rest == "~" || rest.starts_with("~/") {
+        let home = lookup("HOME")
+            .or_else(|| lookup("USERPROFILE"))
+            .ok_or_else(|| anyhow!("Cannot expand '~' in '{}': HOME is not set.", raw))?;
+        expanded.push_str(&home);
+        rest = &rest[1..];
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow!("Unterminated '${{' in '{}'.", raw))?;
+            let reference = &braced[..end];
+            let (name, default) = match reference.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (reference, None),
+            };
+            if !is_variable_name(name) {
+                return Err(anyhow!("Invalid variable name '{}' in '{}'.", name, raw));
+            }
+            let value = match (lookup(name).filter(|value| !value.is_empty()), default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => default.to_string(),
+                (None, None) => {
+                    return Err(anyhow!("Environment variable '{}' used in '{}' is not set.", name, raw))
+                }
+            };
+            expanded.push_str(&value);
+            rest = &braced[end + 1..];
+        } else {
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if len == 0 || !is_variable_name(&after[..len]) {
+                expanded.push('$');
+                rest = after;
+                continue;
+            }
+            let name = &after[..len];
+            let value = lookup(name)
+                .ok_or_else(|| anyhow!("Environment variable '{}' used in '{}' is not set.", name, raw))?;
+            expanded.push_str(&value);
+            rest = &after[len..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(PathBuf::from(expanded))
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(raw: &str) -> Result<String> {
+        let lookup = |name: &str| match name {
+            "HOME" => Some("/home/me".to_string()),
+            "PROJECTS" => Some("/srv/projects".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+        expand_with(raw, lookup).map(|path| path.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn expands_a_leading_tilde() {
+        assert_eq!(expand("~").unwrap(), "/home/me");
+        assert_eq!(expand("~/stacks").unwrap(), "/home/me/stacks");
+        // Only a leading `~` that names the home directory is special.
+        assert_eq!(expand("~other/stacks").unwrap(), "~other/stacks");
+        assert_eq!(expand("a/~/b").unwrap(), "a/~/b");
+    }
+
+    #[test]
+    fn expands_plain_and_braced_variables() {
+        assert_eq!(expand("$PROJECTS/app").unwrap(), "/srv/projects/app");
+        assert_eq!(expand("${PROJECTS}app").unwrap(), "/srv/projectsapp");
+        assert_eq!(expand("$HOME-$PROJECTS").unwrap(), "/home/me-/srv/projects");
+    }
+
+    #[test]
+    fn uses_the_default_for_unset_or_empty_variables() {
+        assert_eq!(expand("${MISSING:-/tmp/stacks}").unwrap(), "/tmp/stacks");
+        assert_eq!(expand("${EMPTY:-fallback}/x").unwrap(), "fallback/x");
+        assert_eq!(expand("${PROJECTS:-unused}").unwrap(), "/srv/projects");
+        assert_eq!(expand("${MISSING:-}").unwrap(), "");
+    }
+
+    #[test]
+    fn rejects_undefined_variables_without_a_default() {
+        assert!(expand("$MISSING/x").unwrap_err().to_string().contains("'MISSING'"));
+        assert!(expand("${MISSING}").unwrap_err().to_string().contains("'MISSING'"));
+    }
+
+    #[test]
+    fn keeps_a_dollar_that_starts_no_reference() {
+        assert_eq!(expand("$").unwrap(), "$");
+        assert_eq!(expand("a$/b").unwrap(), "a$/b");
+        assert_eq!(expand("$1x").unwrap(), "$1x");
+    }
+
+    #[test]
+    fn rejects_malformed_braces() {
+        assert!(expand("${PROJECTS").unwrap_err().to_string().contains("Unterminated"));
+        assert!(expand("${1BAD}").unwrap_err().to_string().contains("Invalid variable name"));
+    }
+}
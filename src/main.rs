@@ -1,49 +1,2250 @@
+/// `println!` for progress output. While `--print-path` reserves stdout for
+/// the created project's path, everything else goes to stderr instead.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if $crate::STDOUT_RESERVED.load(std::sync::atomic::Ordering::Relaxed) {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Records a warning in a [`GenContext`] for the summary printed when the
+/// run ends, so it is not lost among the progress output.
+macro_rules! warning {
+    ($ctx:expr, $($arg:tt)*) => {
+        $ctx.warn(format!($($arg)*))
+    };
+}
+
+mod cargo;
+mod download;
+mod editorconfig;
+mod expand;
+mod manifest;
+mod naming;
+mod proxy;
+mod templates;
+mod updates;
+
 use anyhow::{anyhow, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use console::Term;
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use std::borrow::Cow;
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use which::which;
 
-#[derive(Parser)]
-#[command(name = "Project Generator")]
-#[command(about = "Project to create projects", long_about = None)]
-struct Cli {
-    #[arg(long)]
-    no_prompt: bool,
-    #[command(subcommand)]
-    command: Commands,
+#[derive(Parser)]
+#[command(name = "Project Generator")]
+#[command(about = "Project to create projects", long_about = None)]
+#[command(subcommand_required = true, arg_required_else_help = true)]
+struct Cli {
+    #[arg(long)]
+    no_prompt: bool,
+    /// Directory of TOML stack manifests, instead of the one in the user's config directory;
+    /// `~` and `$VAR`/`${VAR:-default}` are expanded
+    #[arg(long, value_name = "DIR")]
+    template_dir: Option<String>,
+    /// Initialize a git repository and make an initial commit
+    #[arg(long)]
+    git: bool,
+    /// Message for the initial commit; `{project}` is replaced by the project name
+    #[arg(long, requires = "git")]
+    git_commit_message: Option<String>,
+    /// On success, print only the absolute path of the created project to stdout
+    #[arg(long)]
+    print_path: bool,
+    /// Line endings used for every generated file
+    #[arg(long, value_enum, default_value_t = LineEnding::Lf)]
+    line_ending: LineEnding,
+    /// Treat project names that shadow a command or reserved word as an error
+    #[arg(long)]
+    strict_names: bool,
+    /// Fail the run if anything was warned about; implies `--strict-names`
+    #[arg(long)]
+    strict: bool,
+    /// Seed for generated secrets, for reproducible output.
+    /// Seeded secrets are predictable: never use them in production!
+    #[arg(long, value_name = "VALUE")]
+    seed: Option<u64>,
+    /// After generating, warn about template dependency pins that newer releases have outgrown (uses the network)
+    #[arg(long)]
+    check_updates: bool,
+    /// Add the stack's entries to `.gitignore`, merging with an existing one
+    #[arg(long)]
+    gitignore: bool,
+    /// Leave `.gitignore` alone despite `--all-extras`
+    #[arg(long, conflicts_with = "gitignore")]
+    no_gitignore: bool,
+    /// Write a starter OpenAPI 3 spec (openapi.yaml) for stacks that serve HTTP
+    #[arg(long)]
+    openapi: bool,
+    /// Skip the OpenAPI spec despite `--all-extras`
+    #[arg(long, conflicts_with = "openapi")]
+    no_openapi: bool,
+    /// Enable every optional extra that fits the stack (--gitignore, --openapi, --pre-commit)
+    #[arg(long, alias = "full")]
+    all_extras: bool,
+    /// Create the Python virtual environment here instead of `<project>/venv`
+    #[arg(long, value_name = "DIR")]
+    venv_path: Option<String>,
+    /// What to do with a virtual environment left by an earlier run
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t = VenvPolicy::Reuse)]
+    venv: VenvPolicy,
+    /// Symfony CLI executable to use instead of the one on PATH
+    #[arg(long, value_name = "PATH")]
+    symfony_bin: Option<PathBuf>,
+    /// Cargo executable to use instead of the one on PATH
+    #[arg(long, value_name = "PATH")]
+    cargo_bin: Option<PathBuf>,
+    /// Python interpreter to use instead of `python`/`python3` from PATH
+    #[arg(long, value_name = "PATH")]
+    python: Option<PathBuf>,
+    /// Disable colored prompts (also honored: the NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
+    /// Install the project's Python requirements into its virtual environment,
+    /// and the pre-commit hooks with `--pre-commit`
+    #[arg(long)]
+    install: bool,
+    /// Write a double-clickable launcher (run.bat on Windows, run.sh elsewhere) that starts the app
+    #[arg(long)]
+    launcher: bool,
+    /// Write a `.pre-commit-config.yaml` with the stack's formatters and linters
+    #[arg(long)]
+    pre_commit: bool,
+    /// Skip the pre-commit config despite `--all-extras`
+    #[arg(long, conflicts_with = "pre_commit")]
+    no_pre_commit: bool,
+    /// Read one extra file from stdin and write it into the project at `--stdin-template-path`
+    #[arg(long, requires = "stdin_template_path")]
+    stdin_template: bool,
+    /// Project-relative path for the `--stdin-template` file
+    #[arg(long, value_name = "PATH", requires = "stdin_template")]
+    stdin_template_path: Option<String>,
+    /// Write only the stack's package manifest (Cargo.toml, package.json, ...) into the target
+    #[arg(long, conflicts_with_all = ["force", "on_conflict"])]
+    manifest_only: bool,
+    /// Replace the target if it already exists
+    #[arg(long)]
+    force: bool,
+    /// What to do when the target already exists, instead of generating into it
+    #[arg(long, value_enum, value_name = "STRATEGY", conflicts_with = "force")]
+    on_conflict: Option<OnConflict>,
+    /// With `--force`, move the existing target to `<target>.bak-<timestamp>` instead of deleting it
+    #[arg(long, requires = "force")]
+    backup: bool,
+    /// Port for the generated development server, instead of the stack's default
+    #[arg(long, value_name = "N")]
+    port: Option<u16>,
+    /// Print the resolved plan, with warnings about questionable flag combinations, before generating
+    #[arg(long)]
+    describe_plan: bool,
+    /// Explain what the tool does as it goes; implies `--describe-plan`
+    #[arg(long, short)]
+    verbose: bool,
+    /// Report how long each generation step took
+    #[arg(long)]
+    timings: bool,
+    /// Run the prechecks only and report the verdict through the exit code
+    #[arg(long)]
+    dry_run: bool,
+    /// Built-in stacks; `None` when a template manifest subcommand was chosen.
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+impl Cli {
+    /// Whether an extra is on: asked for directly, or implied by
+    /// `--all-extras` and not subtracted with its `--no-` flag.
+    fn extra(&self, enabled: bool, disabled: bool) -> bool {
+        enabled || (self.all_extras && !disabled)
+    }
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Symfony {
+        project: String,
+    },
+    Flask {
+        project: String,
+    },
+    /// Create a Cloudflare Workers project
+    Worker {
+        project: String,
+    },
+    Django {
+        project: String,
+        /// Add Django REST Framework with a sample serializer, viewset and router
+        #[arg(long)]
+        drf: bool,
+    },
+    Rust {
+        project: String,
+        /// Kind of crate to create
+        #[arg(long, value_enum, default_value_t = CrateType::Bin)]
+        crate_type: CrateType,
+        /// Scaffold for a framework on top of the plain crate
+        #[arg(long, value_enum)]
+        framework: Option<RustFramework>,
+        /// Name of the binary, when it should differ from the package name
+        #[arg(long, value_name = "NAME")]
+        rust_bin_name: Option<String>,
+        /// Extra features for a dependency the template adds, e.g. `yew=ssr`; repeatable
+        #[arg(long, value_name = "CRATE=FEATURES", value_parser = cargo::parse_crate_features)]
+        rust_features: Vec<cargo::CrateFeatures>,
+    },
+    /// Check which of the tools used by the generators are installed
+    Doctor,
+    /// List the available stacks and whether their tools are installed
+    List {
+        /// Print a machine-readable JSON array instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rewrite one scaffolded file of an existing project from its built-in template
+    ResetFile {
+        project: String,
+        /// Path of the file, relative to the project directory
+        path: String,
+    },
+}
+
+/// An HTTP endpoint a stack's sample app serves, for the OpenAPI spec.
+struct ApiPath {
+    path: &'static str,
+    summary: &'static str,
+    content_type: &'static str,
+}
+
+/// Set by `--print-path`; see [`say!`].
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--verbose`; enables [`explain_missing`].
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CrateType {
+    Bin,
+    Lib,
+    /// Library with `proc-macro = true` and a sample derive macro
+    ProcMacro,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RustFramework {
+    /// WebAssembly frontend built with trunk
+    Yew,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OnConflict {
+    /// Stop with an error
+    Error,
+    /// Append -1, -2, ... to the project name until the target is free
+    Rename,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum VenvPolicy {
+    /// Keep an existing environment as it is
+    Reuse,
+    /// Delete an existing environment and create a fresh one
+    Recreate,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LineEnding {
+    Lf,
+    Crlf,
+    /// CRLF on Windows, LF everywhere else
+    Native,
+}
+
+/// Set from `--line-ending` before anything is generated.
+static LINE_ENDING: OnceLock<LineEnding> = OnceLock::new();
+
+/// Source of generated secrets; seeded from `--seed` or the OS. ChaCha20's
+/// output for a seed is fixed across versions and platforms, unlike `StdRng`.
+static SECRET_RNG: OnceLock<Mutex<ChaCha20Rng>> = OnceLock::new();
+
+/// Placeholder documented in `.env.example` for values that get a generated
+/// secret in `.env`.
+const SECRET_PLACEHOLDER: &str = "change-me";
+
+const SECRET_LENGTH: usize = 50;
+const SECRET_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_";
+
+fn generate_secret() -> String {
+    let rng = SECRET_RNG.get_or_init(|| Mutex::new(ChaCha20Rng::from_entropy()));
+    secret_from(&mut *rng.lock().unwrap())
+}
+
+/// The alphabet has 64 symbols, so six bits of each word pick one without
+/// bias and without depending on rand's range sampling.
+fn secret_from(rng: &mut impl RngCore) -> String {
+    (0..SECRET_LENGTH)
+        .map(|_| SECRET_ALPHABET[rng.next_u32() as usize % SECRET_ALPHABET.len()] as char)
+        .collect()
+}
+
+/// Whether prompts may use color; resolved once from `--no-color`, the
+/// `NO_COLOR` convention and the capabilities of stderr, where prompts go.
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Executables given with `--symfony-bin`, `--cargo-bin` and `--python`,
+/// keyed by the command name they stand in for; see [`resolve_tool`].
+static TOOL_OVERRIDES: OnceLock<Vec<(&'static str, PathBuf)>> = OnceLock::new();
+
+fn colors_supported(no_color: bool) -> bool {
+    !no_color
+        && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        && Term::stderr().features().colors_supported()
+}
+
+/// Where child processes send their stdout, so `--print-path` output stays clean.
+fn child_stdout() -> Stdio {
+    if STDOUT_RESERVED.load(Ordering::Relaxed) {
+        Stdio::from(std::io::stderr())
+    } else {
+        Stdio::inherit()
+    }
+}
+
+/// The stack selected on the command line.
+enum Stack<'a> {
+    Symfony,
+    Flask,
+    Django { drf: bool },
+    Worker,
+    Rust {
+        crate_type: CrateType,
+        framework: Option<RustFramework>,
+        bin_name: Option<&'a str>,
+        features: &'a [cargo::CrateFeatures],
+    },
+    Manifest(&'a manifest::ManifestGenerator),
+}
+
+/// A tool a stack delegates to. Any of `names` satisfies it; when
+/// `has_fallback` is set the stack can scaffold without it.
+struct ToolRequirement<'a> {
+    names: Vec<&'a str>,
+    has_fallback: bool,
+}
+
+impl<'a> Stack<'a> {
+    fn name(&self) -> &'a str {
+        match self {
+            Stack::Symfony => "symfony",
+            Stack::Flask => "flask",
+            Stack::Django { .. } => "django",
+            Stack::Worker => "worker",
+            Stack::Rust { .. } => "rust",
+            Stack::Manifest(generator) => &generator.name,
+        }
+    }
+
+    /// Words the stack's language or tooling reserves, which make poor project names.
+    fn reserved_names(&self) -> &'static [&'static str] {
+        match self {
+            Stack::Symfony => PHP_RESERVED_NAMES,
+            Stack::Flask | Stack::Django { .. } => PYTHON_RESERVED_NAMES,
+            Stack::Worker => JS_RESERVED_NAMES,
+            Stack::Rust { .. } => RUST_RESERVED_NAMES,
+            Stack::Manifest(_) => &[],
+        }
+    }
+
+    /// Built-in templates of the files this stack scaffolds, keyed by their
+    /// path relative to the project directory. Server configs keep their
+    /// `{port}` placeholder for [`with_port`].
+    fn templates(&self) -> Vec<(&'static str, Cow<'static, str>)> {
+        let fixed: &[(&str, &str)] = match self {
+            Stack::Symfony => &[("public/index.php", templates::SYMFONY_INDEX_PHP)],
+            Stack::Flask => &[("app/app.py", templates::FLASK_APP_PY)],
+            Stack::Worker => &[
+                ("src/index.js", templates::WORKER_INDEX_JS),
+                ("wrangler.toml", templates::WRANGLER_TOML),
+            ],
+            Stack::Django { drf: false } => &[
+                ("manage.py", templates::DJANGO_MANAGE_PY),
+                ("project/settings.py", templates::DJANGO_SETTINGS_PY),
+            ],
+            Stack::Django { drf: true } => {
+                let mut files = vec![
+                    ("manage.py", Cow::Borrowed(templates::DJANGO_MANAGE_PY)),
+                    (
+                        "project/settings.py",
+                        Cow::Owned(drf_settings_template()),
+                    ),
+                    ("project/urls.py", Cow::Borrowed(templates::DJANGO_DRF_URLS_PY)),
+                ];
+                files.extend(
+                    DRF_API_FILES
+                        .iter()
+                        .map(|&(path, content)| (path, Cow::Borrowed(content))),
+                );
+                return files;
+            }
+            Stack::Rust {
+                framework: Some(RustFramework::Yew),
+                ..
+            } => &[
+                ("src/main.rs", templates::YEW_MAIN_RS),
+                ("index.html", templates::YEW_INDEX_HTML),
+                ("Trunk.toml", templates::TRUNK_TOML),
+            ],
+            Stack::Rust {
+                crate_type: CrateType::Bin,
+                ..
+            } => &[("src/main.rs", templates::RUST_MAIN_RS)],
+            Stack::Rust {
+                crate_type: CrateType::ProcMacro,
+                ..
+            } => &[("src/lib.rs", templates::PROC_MACRO_LIB_RS)],
+            Stack::Rust {
+                crate_type: CrateType::Lib,
+                ..
+            }
+            | Stack::Manifest(_) => &[],
+        };
+        fixed
+            .iter()
+            .map(|&(path, content)| (path, Cow::Borrowed(content)))
+            .collect()
+    }
+
+    /// Port the stack's development server listens on; `None` for stacks
+    /// that serve nothing.
+    fn default_port(&self) -> Option<u16> {
+        match self {
+            Stack::Flask => Some(5000),
+            Stack::Django { .. } | Stack::Symfony => Some(8000),
+            Stack::Worker => Some(8787),
+            Stack::Rust {
+                framework: Some(RustFramework::Yew),
+                ..
+            } => Some(8080),
+            Stack::Rust { .. } | Stack::Manifest(_) => None,
+        }
+    }
+
+    /// Command that starts the development server on `port`.
+    fn serve_hint(&self, port: u16) -> Option<String> {
+        match self {
+            Stack::Flask => Some("python app/app.py".to_string()),
+            Stack::Django { .. } => Some(format!("python manage.py runserver {}", port)),
+            Stack::Symfony => Some(format!("symfony serve --port={}", port)),
+            Stack::Worker => Some("npx wrangler dev".to_string()),
+            Stack::Rust {
+                framework: Some(RustFramework::Yew),
+                ..
+            } => Some("trunk serve".to_string()),
+            Stack::Rust { .. } | Stack::Manifest(_) => None,
+        }
+    }
+
+    /// Command that runs the generated project: its development server, or
+    /// `cargo run` for plain binaries.
+    fn run_command(&self, port: Option<u16>) -> Option<String> {
+        match self {
+            Stack::Rust {
+                crate_type: CrateType::Bin,
+                framework: None,
+                ..
+            } => Some("cargo run".to_string()),
+            _ => port.and_then(|port| self.serve_hint(port)),
+        }
+    }
+
+    /// Endpoints of the generated sample app; empty for stacks without one.
+    fn api_paths(&self) -> &'static [ApiPath] {
+        match self {
+            Stack::Flask => &[ApiPath {
+                path: "/",
+                summary: "Greeting from the sample route",
+                content_type: "text/plain",
+            }],
+            Stack::Symfony => &[ApiPath {
+                path: "/",
+                summary: "Front controller",
+                content_type: "text/html",
+            }],
+            Stack::Worker => &[ApiPath {
+                path: "/",
+                summary: "Greeting from the fetch handler",
+                content_type: "text/plain",
+            }],
+            // The DefaultRouter of `api/urls.py`, mounted at `api/`.
+            Stack::Django { drf: true } => &[
+                ApiPath {
+                    path: "/api/",
+                    summary: "Browsable root of the REST API",
+                    content_type: "application/json",
+                },
+                ApiPath {
+                    path: "/api/users/",
+                    summary: "Users, from the sample UserViewSet",
+                    content_type: "application/json",
+                },
+            ],
+            _ => &[],
+        }
+    }
+
+    /// `repos:` entries of the `.pre-commit-config.yaml` written by `--pre-commit`.
+    fn pre_commit_repos(&self) -> &'static [&'static str] {
+        match self {
+            Stack::Symfony => &[templates::PRE_COMMIT_BASE, templates::PRE_COMMIT_PHP],
+            Stack::Flask | Stack::Django { .. } => &[templates::PRE_COMMIT_BASE, templates::PRE_COMMIT_PYTHON],
+            Stack::Worker => &[templates::PRE_COMMIT_BASE, templates::PRE_COMMIT_JS],
+            Stack::Rust { .. } => &[templates::PRE_COMMIT_BASE, templates::PRE_COMMIT_RUST],
+            Stack::Manifest(_) => &[templates::PRE_COMMIT_BASE],
+        }
+    }
+
+    fn uses_virtualenv(&self) -> bool {
+        matches!(self, Stack::Flask | Stack::Django { .. })
+    }
+
+    /// Packages a Python stack needs, for `requirements.txt` and `pyproject.toml`.
+    fn python_dependencies(&self) -> &'static [&'static str] {
+        match self {
+            Stack::Flask => &["flask"],
+            Stack::Django { drf: false } => &["django"],
+            Stack::Django { drf: true } => &["django", "djangorestframework"],
+            _ => &[],
+        }
+    }
+
+    /// Entries `--gitignore` makes sure the project's `.gitignore` has. The
+    /// virtual environment is added separately, since it may live elsewhere.
+    fn gitignore_entries(&self) -> Vec<&'a str> {
+        match self {
+            Stack::Symfony => vec!["/vendor/", "/var/", ".env", ".env.local", ".env.*.local"],
+            Stack::Flask => vec!["__pycache__/", "*.pyc", "instance/", ".env"],
+            Stack::Django { .. } => vec!["__pycache__/", "*.pyc", "db.sqlite3", "staticfiles/", ".env"],
+            Stack::Rust {
+                framework: Some(RustFramework::Yew),
+                ..
+            } => vec!["/target", "/dist"],
+            Stack::Worker => vec!["node_modules/", ".wrangler/", ".dev.vars"],
+            Stack::Rust { .. } => vec!["/target"],
+            Stack::Manifest(generator) => generator.gitignore.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Dependency versions the stack's templates pin.
+    fn pinned_dependencies(&self) -> Vec<updates::PinnedDependency<'static>> {
+        let pins = match self {
+            Stack::Rust {
+                crate_type,
+                framework,
+                ..
+            } => template_dependencies(*crate_type, *framework),
+            _ => &[],
+        };
+        pins.iter()
+            .map(|&(name, version, _)| updates::PinnedDependency { name, version })
+            .collect()
+    }
+
+    /// Checks that need no tools or filesystem: everything the generator
+    /// would reject about the options or the names derived from `project`.
+    fn precheck(&self, project: &str) -> Result<()> {
+        match self {
+            Stack::Rust {
+                crate_type,
+                framework,
+                bin_name,
+                features,
+            } => check_rust_options(project, *crate_type, *framework, *bin_name, features).map(drop),
+            _ => Ok(()),
+        }
+    }
+
+    /// The package manifest `--manifest-only` writes, if the stack has one.
+    fn manifest_file_name(&self) -> Option<&'static str> {
+        match self {
+            Stack::Rust { .. } => Some("Cargo.toml"),
+            Stack::Flask | Stack::Django { .. } => Some("pyproject.toml"),
+            Stack::Symfony => Some("composer.json"),
+            Stack::Worker => Some("package.json"),
+            Stack::Manifest(_) => None,
+        }
+    }
+
+    /// Whether generation hands the target to a tool that refuses any
+    /// existing destination, so even an empty directory is a conflict.
+    fn needs_fresh_target(&self) -> bool {
+        match self {
+            Stack::Rust { .. } => true,
+            Stack::Symfony => command_exists("symfony"),
+            _ => false,
+        }
+    }
+
+    fn requirements(&self) -> Vec<ToolRequirement<'a>> {
+        let tool = |names: &[&'a str], has_fallback| ToolRequirement {
+            names: names.to_vec(),
+            has_fallback,
+        };
+        match self {
+            Stack::Symfony => vec![tool(&["symfony"], true)],
+            Stack::Flask => vec![tool(&["python", "python3"], false)],
+            Stack::Worker => vec![tool(&["npm"], true)],
+            Stack::Django { .. } => vec![
+                tool(&["django-admin"], true),
+                tool(&["python", "python3"], false),
+            ],
+            Stack::Rust { .. } => vec![tool(&["cargo"], false)],
+            Stack::Manifest(generator) => generator
+                .requires
+                .iter()
+                .map(|name| tool(&[name.as_str()], false))
+                .collect(),
+        }
+    }
+}
+
+/// A dependency a Rust template adds: (crate, version requirement, features).
+type CrateDependency = (&'static str, &'static str, &'static [&'static str]);
+
+/// The crates the Rust templates add for a crate type and framework.
+fn template_dependencies(crate_type: CrateType, framework: Option<RustFramework>) -> &'static [CrateDependency] {
+    match (crate_type, framework) {
+        (_, Some(RustFramework::Yew)) => YEW_DEPENDENCIES,
+        (CrateType::ProcMacro, None) => PROC_MACRO_DEPENDENCIES,
+        _ => &[],
+    }
+}
+
+const PROC_MACRO_DEPENDENCIES: &[CrateDependency] = &[
+    ("proc-macro2", "1", &[]),
+    ("quote", "1", &[]),
+    ("syn", "2", &[]),
+];
+
+const YEW_DEPENDENCIES: &[CrateDependency] = &[("yew", "0.21", &["csr"]), ("wasm-bindgen", "0.2", &[])];
+
+/// The sample REST API package `--drf` adds, by project-relative path.
+const DRF_API_FILES: &[(&str, &str)] = &[
+    ("api/__init__.py", ""),
+    ("api/serializers.py", templates::DRF_SERIALIZERS_PY),
+    ("api/views.py", templates::DRF_VIEWS_PY),
+    ("api/urls.py", templates::DRF_API_URLS_PY),
+];
+
+const RUST_RESERVED_NAMES: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while", "std", "core", "alloc", "test", "proc_macro",
+];
+
+const PYTHON_RESERVED_NAMES: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+    "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+    "try", "while", "with", "yield", "test", "flask", "django", "site", "os", "sys",
+];
+
+const JS_RESERVED_NAMES: &[&str] = &[
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+    "do", "else", "export", "extends", "function", "if", "import", "in", "new", "return",
+    "super", "switch", "this", "throw", "try", "typeof", "var", "void", "while", "node_modules",
+    "wrangler",
+];
+
+const PHP_RESERVED_NAMES: &[&str] = &[
+    "abstract", "array", "class", "const", "echo", "function", "global", "interface", "list",
+    "namespace", "new", "parent", "private", "protected", "public", "static", "symfony",
+    "trait", "use", "var",
+];
+
+/// `--dry-run` exit code when every precheck passed.
+const EXIT_PLAN_OK: i32 = 0;
+/// `--dry-run` exit code when the generator would reject the options or the
+/// names derived from the project, like clap does for malformed arguments.
+const EXIT_INVALID_OPTIONS: i32 = 2;
+/// `--dry-run` exit code when a required tool is missing.
+const EXIT_MISSING_TOOL: i32 = 3;
+/// `--dry-run` exit code when the target directory exists or is not writable.
+const EXIT_TARGET_CONFLICT: i32 = 4;
+
+const DEFAULT_GIT_COMMIT_MESSAGE: &str = "Initial commit";
+
+/// Directory under the user's config directory scanned at startup for TOML
+/// stack manifests.
+const USER_TEMPLATE_DIR: &str = "project_generator_cli/templates";
+
+/// Tools probed by `doctor`, in the order they are reported.
+const DOCTOR_TOOLS: &[&str] = &[
+    "symfony",
+    "php",
+    "composer",
+    "python",
+    "python3",
+    "pip",
+    "django-admin",
+    "node",
+    "npm",
+    "cargo",
+    "rustc",
+    "git",
+];
+
+/// Upper bound on concurrently running `--version` probes.
+const MAX_PARALLEL_PROBES: usize = 4;
+
+/// What the steps of one run share: the warnings recorded with [`warning!`]
+/// and whether `--strict` turns them into a failure.
+#[derive(Default)]
+struct GenContext {
+    warnings: Vec<String>,
+    strict: bool,
+}
+
+impl GenContext {
+    /// Records a warning; one the plan already recorded is not repeated.
+    fn warn(&mut self, message: String) {
+        if !self.warnings.contains(&message) {
+            self.warnings.push(message);
+        }
+    }
+
+    /// Prints the recorded warnings under one heading.
+    fn report_warnings(&self) {
+        if self.warnings.is_empty() {
+            return;
+        }
+        say!("");
+        say!("Warnings:");
+        for warning in &self.warnings {
+            say!("  - {}", warning);
+        }
+    }
+
+    /// Under `--strict`, the error that fails the run because of the first warning.
+    fn strict_failure(&self) -> Option<anyhow::Error> {
+        if !self.strict {
+            return None;
+        }
+        let first = self.warnings.first()?;
+        Some(anyhow!(
+            "--strict: failing because of {} warning(s), first: {}",
+            self.warnings.len(),
+            first
+        ))
+    }
+}
+
+fn main() -> Result<()> {
+    let mut ctx = GenContext::default();
+    let result = run(&mut ctx);
+    ctx.report_warnings();
+    result?;
+    ctx.strict_failure().map_or(Ok(()), Err)
+}
+
+fn run(ctx: &mut GenContext) -> Result<()> {
+    let template_dir = match template_dir_arg() {
+        Some(dir) => Some(expand::expand_path(&dir)?),
+        None => user_template_dir(),
+    };
+    let generators: Vec<_> = template_dir
+        .map(|dir| manifest::load_manifest_generators(ctx, &dir))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|generator| {
+            let builtin = Commands::has_subcommand(&generator.name);
+            if builtin {
+                warning!(
+                    ctx,
+                    "Ignoring template manifest '{}': it shadows a built-in subcommand.",
+                    generator.name
+                );
+            }
+            !builtin
+        })
+        .collect();
+    let mut command = Cli::command();
+    for generator in &generators {
+        command = command.subcommand(generator.subcommand());
+    }
+    let matches = command.get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    STDOUT_RESERVED.store(cli.print_path, Ordering::Relaxed);
+    VERBOSE.store(cli.verbose, Ordering::Relaxed);
+    ctx.strict = cli.strict;
+    COLOR_ENABLED.store(colors_supported(cli.no_color), Ordering::Relaxed);
+    if !COLOR_ENABLED.load(Ordering::Relaxed) {
+        console::set_colors_enabled_stderr(false);
+    }
+    LINE_ENDING.get_or_init(|| cli.line_ending);
+    let mut overrides = Vec::new();
+    for (names, path) in [
+        (&["symfony"][..], &cli.symfony_bin),
+        (&["cargo"][..], &cli.cargo_bin),
+        (&["python", "python3"][..], &cli.python),
+    ] {
+        if let Some(path) = path {
+            check_executable(path)?;
+            overrides.extend(names.iter().map(|&name| (name, path.clone())));
+        }
+    }
+    TOOL_OVERRIDES.get_or_init(|| overrides);
+    if let Some(seed) = cli.seed {
+        SECRET_RNG.get_or_init(|| Mutex::new(ChaCha20Rng::seed_from_u64(seed)));
+    }
+    let commit_message = match &cli.git_commit_message {
+        Some(message) if message.trim().is_empty() => {
+            return Err(anyhow!("The git commit message must not be empty."));
+        }
+        Some(message) => message.as_str(),
+        None => DEFAULT_GIT_COMMIT_MESSAGE,
+    };
+    let (stack, project) = match &cli.command {
+        Some(Commands::Symfony { project }) => (Stack::Symfony, project.as_str()),
+        Some(Commands::Flask { project }) => (Stack::Flask, project.as_str()),
+        Some(Commands::Worker { project }) => (Stack::Worker, project.as_str()),
+        Some(Commands::Django { project, drf }) => (Stack::Django { drf: *drf }, project.as_str()),
+        Some(Commands::Rust {
+            project,
+            crate_type,
+            framework,
+            rust_bin_name,
+            rust_features,
+        }) => (
+            Stack::Rust {
+                crate_type: *crate_type,
+                framework: *framework,
+                bin_name: rust_bin_name.as_deref(),
+                features: rust_features,
+            },
+            project.as_str(),
+        ),
+        Some(Commands::Doctor) => {
+            run_doctor();
+            return Ok(());
+        }
+        Some(Commands::ResetFile { project, path }) => {
+            return reset_file(project, path, cli.port, cli.no_prompt);
+        }
+        Some(Commands::List { json }) => {
+            list_stacks(&generators, *json);
+            return Ok(());
+        }
+        None => {
+            let (name, sub_matches) = matches.subcommand().expect("a subcommand is required");
+            let generator = generators
+                .iter()
+                .find(|generator| generator.name == name)
+                .expect("only manifest subcommands are left unparsed");
+            let project = sub_matches
+                .get_one::<String>("project")
+                .expect("project is a required argument");
+            (Stack::Manifest(generator), project.as_str())
+        }
+    };
+    let renamed;
+    let project = match cli.on_conflict {
+        Some(_) if fs::symlink_metadata(project).is_err() => project,
+        // The dry run reports the conflict itself, with its own exit code.
+        Some(OnConflict::Error) if cli.dry_run => project,
+        Some(OnConflict::Error) => {
+            return Err(anyhow!("The target {:?} already exists.", project));
+        }
+        Some(OnConflict::Rename) => {
+            renamed = free_project_name(project)?;
+            say!("{:?} already exists; generating into {:?} instead.", project, renamed);
+            renamed.as_str()
+        }
+        None => project,
+    };
+    check_project_name(ctx, &stack, project, cli.strict_names || cli.strict)?;
+    let port = match (cli.port, stack.default_port()) {
+        (Some(_), None) => {
+            warning!(ctx, "{} projects run no server; ignoring --port.", stack.name());
+            None
+        }
+        (port, default) => port.or(default),
+    };
+    if cli.manifest_only {
+        let ignored: Vec<&str> = [
+            (cli.git, "--git"),
+            (cli.gitignore, "--gitignore"),
+            (cli.pre_commit, "--pre-commit"),
+            (cli.openapi, "--openapi"),
+            (cli.install, "--install"),
+            (cli.all_extras, "--all-extras"),
+            (cli.check_updates, "--check-updates"),
+            (cli.launcher, "--launcher"),
+            (cli.stdin_template, "--stdin-template"),
+        ]
+        .into_iter()
+        .filter_map(|(given, flag)| given.then_some(flag))
+        .collect();
+        if !ignored.is_empty() {
+            warning!(ctx, "--manifest-only writes only the package manifest; ignoring {}.", ignored.join(", "));
+        }
+    }
+    // Read before anything is generated so a bad template leaves no half-made project.
+    let stdin_template = match &cli.stdin_template_path {
+        Some(relative) if !cli.manifest_only => Some(read_stdin_template(relative, project)?),
+        _ => None,
+    };
+    let extras_given = cli.git || cli.gitignore || cli.pre_commit || cli.openapi || cli.install || cli.all_extras;
+    if !cli.no_prompt && !cli.dry_run && !cli.manifest_only && !extras_given && Term::stderr().is_term() {
+        for extra in prompt_extras(&stack) {
+            match extra {
+                "git" => cli.git = true,
+                "gitignore" => cli.gitignore = true,
+                "pre-commit" => cli.pre_commit = true,
+                "openapi" => cli.openapi = true,
+                "install" => cli.install = true,
+                _ => unreachable!("only offered extras are returned"),
+            }
+        }
+    }
+    let venv_dir = match &cli.venv_path {
+        _ if !stack.uses_virtualenv() => None,
+        Some(path) => Some(expand::expand_path(path)?),
+        None => Some(Path::new(project).join("venv")),
+    };
+    // `--all-extras` only implies the spec where there is something to describe.
+    let openapi = cli.openapi || (cli.extra(false, cli.no_openapi) && !stack.api_paths().is_empty());
+    let pre_commit = cli.extra(cli.pre_commit, cli.no_pre_commit);
+    let gitignore = cli.extra(cli.gitignore, cli.no_gitignore);
+    if cli.describe_plan || cli.verbose {
+        let mut extras = Vec::new();
+        for (enabled, name) in [
+            (gitignore, "gitignore"),
+            (openapi, "openapi"),
+            (pre_commit, "pre-commit"),
+            (cli.install, "install"),
+            (cli.git, "git"),
+            (cli.check_updates, "check-updates"),
+            (stdin_template.is_some(), "stdin-template"),
+            (cli.launcher, "launcher"),
+        ] {
+            if enabled && !cli.manifest_only {
+                extras.push(name);
+            }
+        }
+        describe_plan(ctx, &cli, &stack, project, &extras, venv_dir.as_deref(), port);
+    }
+    if cli.dry_run {
+        let mut code = dry_run(&cli, &stack, project);
+        ctx.report_warnings();
+        if let (0, Some(e)) = (code, ctx.strict_failure()) {
+            eprintln!("Error: {}", e);
+            code = 1;
+        }
+        std::process::exit(code);
+    }
+    if let Some(link) = dangling_link(Path::new(project)) {
+        return Err(anyhow!("Cannot create the project through {:?}: the symlink points to a missing path.", link));
+    }
+    if cli.manifest_only {
+        write_package_manifest(&stack, project)?;
+        if cli.print_path {
+            print_project_path(project)?;
+        }
+        return Ok(());
+    }
+    if !cli.force {
+        if let Some(problem) = target_conflict(Path::new(project), stack.needs_fresh_target()) {
+            return Err(anyhow!(
+                "The {}. Pass --force to replace it or --on-conflict rename to generate next to it.",
+                problem
+            ));
+        }
+    }
+    let resolved;
+    let mut project = project;
+    let mut backup = None;
+    if cli.force && fs::symlink_metadata(project).is_ok() {
+        let target = replacement_target(Path::new(project))?;
+        if target != Path::new(project) {
+            say!("{:?} is a symlink; replacing {:?}, which it points to.", project, target);
+            resolved = target
+                .to_str()
+                .ok_or_else(|| anyhow!("The symlink target {:?} is not valid UTF-8.", target))?
+                .to_string();
+            project = resolved.as_str();
+        }
+        if cli.backup {
+            backup = Some(Backup::create(Path::new(project))?);
+        } else {
+            remove_path(Path::new(project))?;
+        }
+    }
+    let mut timings = Timings::new();
+    timings.step("scaffold", || match stack {
+        Stack::Symfony => create_symfony_project(ctx, project, cli.no_prompt),
+        Stack::Flask => create_flask_project(ctx, project, cli.no_prompt, port.unwrap_or_default()),
+        Stack::Worker => create_worker_project(ctx, project, cli.no_prompt, port.unwrap_or_default()),
+        Stack::Django { drf } => create_django_project(ctx, project, cli.no_prompt, drf),
+        Stack::Rust {
+            crate_type,
+            framework,
+            bin_name,
+            features,
+        } => create_rust_project(ctx, project, crate_type, framework, bin_name, features, port),
+        Stack::Manifest(generator) => generator.generate(project),
+    })?;
+    if cli.seed.is_some() && Path::new(project).join(".env").is_file() {
+        warning!(ctx, "The secrets in .env come from --seed and are predictable; never deploy them.");
+    }
+    if let Some(venv_dir) = &venv_dir {
+        timings.step("venv", || create_virtualenv(ctx, venv_dir, cli.venv, cli.no_prompt));
+        if cli.install {
+            timings.step("install", || install_requirements(ctx, Path::new(project), venv_dir))?;
+        }
+    } else if cli.venv_path.is_some() {
+        warning!(ctx, "{} projects have no virtual environment; ignoring --venv-path.", stack.name());
+    }
+    if let Some((relative, content)) = &stdin_template {
+        timings.step("stdin-template", || {
+            let path = Path::new(project).join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| anyhow!("Failed to create directory {:?}: {}", parent, e))?;
+            }
+            write_file(&path, &manifest::substitute(content, project))
+        })?;
+    }
+    if cli.launcher && Path::new(project).is_dir() {
+        timings.step("launcher", || write_launcher(ctx, Path::new(project), &stack, venv_dir.as_deref(), port))?;
+    }
+    if openapi && Path::new(project).is_dir() {
+        timings.step("openapi", || write_openapi_spec(ctx, Path::new(project), &stack, port))?;
+    }
+    let venv_entry = venv_dir
+        .as_deref()
+        .and_then(|venv_dir| venv_gitignore_entry(Path::new(project), venv_dir));
+    if gitignore && Path::new(project).is_dir() {
+        let mut entries = stack.gitignore_entries();
+        entries.extend(venv_entry.as_deref());
+        timings.step("gitignore", || merge_gitignore(Path::new(project), &entries))?;
+    } else if let Some(entry) = &venv_entry {
+        // Never let a `.gitignore` (or the first commit) take in the venv.
+        if cli.git || Path::new(project).join(".gitignore").is_file() {
+            merge_gitignore(Path::new(project), &[entry.as_str()])?;
+        }
+    }
+    if pre_commit && Path::new(project).is_dir() {
+        timings.step("pre-commit", || {
+            write_file(
+                &Path::new(project).join(".pre-commit-config.yaml"),
+                &format!("repos:\n{}", stack.pre_commit_repos().concat()),
+            )
+        })?;
+    }
+    if cli.git {
+        timings.step("git", || {
+            init_git_repository(project, &commit_message.replace("{project}", project))
+        })?;
+    }
+    if pre_commit && cli.install && Path::new(project).is_dir() {
+        timings.step("pre-commit-install", || install_pre_commit_hooks(ctx, Path::new(project)))?;
+    }
+    if cli.check_updates {
+        timings.step("check-updates", || {
+            updates::check_updates(ctx, &stack.pinned_dependencies())
+        });
+    }
+    if let Some(hint) = port.and_then(|port| stack.serve_hint(port)) {
+        if Path::new(project).is_dir() {
+            say!("Start the development server with: cd {} && {}", project, hint);
+        }
+    }
+    if let Some(backup) = backup {
+        say!("The previous {:?} was moved to {:?}.", project, backup.keep());
+    }
+    if cli.timings {
+        timings.report();
+    }
+    if cli.print_path {
+        print_project_path(project)?;
+    }
+    Ok(())
+}
+
+/// `--print-path`: the project's absolute path, with symlinks resolved, as
+/// the only line on stdout.
+fn print_project_path(project: &str) -> Result<()> {
+    let path = fs::canonicalize(project)
+        .map_err(|e| anyhow!("Failed to resolve project path {:?}: {}", project, e))?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// An existing target moved aside by `--force --backup`. Dropping it before
+/// [`Backup::keep`] puts the original back, so a failed run loses nothing.
+struct Backup {
+    target: PathBuf,
+    backup: PathBuf,
+    kept: bool,
+}
+
+impl Backup {
+    fn create(target: &Path) -> Result<Self> {
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let name = target.file_name().unwrap_or(target.as_os_str()).to_string_lossy();
+        let backup = target.with_file_name(format!("{}.bak-{}", name, stamp));
+        fs::rename(target, &backup)
+            .map_err(|e| anyhow!("Failed to back up {:?} to {:?}: {}", target, backup, e))?;
+        Ok(Backup {
+            target: target.to_path_buf(),
+            backup,
+            kept: false,
+        })
+    }
+
+    /// The run succeeded: leave the backup for the user and return its path.
+    fn keep(mut self) -> PathBuf {
+        self.kept = true;
+        self.backup.clone()
+    }
+}
+
+impl Drop for Backup {
+    fn drop(&mut self) {
+        if self.kept {
+            return;
+        }
+        if fs::symlink_metadata(&self.target).is_ok() {
+            if let Err(e) = remove_path(&self.target) {
+                eprintln!("Failed to remove the partial {:?}: {}", self.target, e);
+                eprintln!("The original is kept in {:?}.", self.backup);
+                return;
+            }
+        }
+        match fs::rename(&self.backup, &self.target) {
+            Ok(()) => eprintln!("Restored {:?} from its backup.", self.target),
+            Err(e) => eprintln!("Failed to restore {:?} from {:?}: {}", self.target, self.backup, e),
+        }
+    }
+}
+
+/// Deletes a file, symlink or whole directory tree.
+fn remove_path(path: &Path) -> Result<()> {
+    let is_dir = fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir());
+    let removed = if is_dir { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    removed.map_err(|e| anyhow!("Failed to remove {:?}: {}", path, e))
+}
+
+/// Wall-clock time of each generation step, for `--timings`.
+struct Timings {
+    started: Instant,
+    steps: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    fn new() -> Self {
+        Timings {
+            started: Instant::now(),
+            steps: Vec::new(),
+        }
+    }
+
+    fn step<T>(&mut self, name: &'static str, run: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = run();
+        self.steps.push((name, started.elapsed()));
+        result
+    }
+
+    fn report(&self) {
+        say!("Timings:");
+        for (name, duration) in &self.steps {
+            say!("  {:<14} {:>8.2}s", name, duration.as_secs_f64());
+        }
+        say!("  {:<14} {:>8.2}s", "total", self.started.elapsed().as_secs_f64());
+    }
+}
+
+/// Recognizes which built-in stack generated the project in `base` from the
+/// files it scaffolds.
+fn detect_stack(base: &Path) -> Option<Stack<'static>> {
+    if base.join("Cargo.toml").is_file() {
+        let crate_type = if cargo::is_proc_macro(base) {
+            CrateType::ProcMacro
+        } else if base.join("src").join("main.rs").is_file() {
+            CrateType::Bin
+        } else {
+            CrateType::Lib
+        };
+        let framework = base.join("Trunk.toml").is_file().then_some(RustFramework::Yew);
+        Some(Stack::Rust {
+            crate_type,
+            framework,
+            bin_name: None,
+            features: &[],
+        })
+    } else if django_settings_package(base).is_some() {
+        Some(Stack::Django {
+            drf: base.join("api").join("serializers.py").is_file(),
+        })
+    } else if base.join("wrangler.toml").is_file() {
+        Some(Stack::Worker)
+    } else if base.join("app").join("app.py").is_file() {
+        Some(Stack::Flask)
+    } else if base.join("public").join("index.php").is_file() {
+        Some(Stack::Symfony)
+    } else {
+        None
+    }
+}
+
+/// The package of `settings.py` that a Django project's `manage.py` points
+/// `DJANGO_SETTINGS_MODULE` at: `project` for the fallback scaffold, the
+/// module name for `django-admin startproject`.
+fn django_settings_package(base: &Path) -> Option<String> {
+    let manage_py = fs::read_to_string(base.join("manage.py")).ok()?;
+    let line = manage_py.lines().find(|line| line.contains("DJANGO_SETTINGS_MODULE"))?;
+    let module = line.split(['\'', '"']).find(|part| part.ends_with(".settings"))?;
+    let package = module.strip_suffix(".settings")?;
+    let settings = base.join(package.replace('.', "/")).join("settings.py");
+    settings.is_file().then(|| package.to_string())
+}
+
+/// The port a previously generated `existing` file has where `template` has
+/// `{port}`, so a reset keeps the `--port` it was generated with.
+fn existing_port(template: &str, existing: &str) -> Option<u16> {
+    let line = template.lines().find(|line| line.contains("{port}"))?;
+    let before = &line[..line.find("{port}")?];
+    let key = &before[before.rfind("port")?..];
+    let start = existing.find(key)? + key.len();
+    let digits: String = existing[start..].chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+fn reset_file(project: &str, path: &str, port: Option<u16>, no_prompt: bool) -> Result<()> {
+    let base = Path::new(project);
+    let stack = detect_stack(base)
+        .ok_or_else(|| anyhow!("Could not detect a stack with built-in templates in {:?}.", base))?;
+    let wanted: PathBuf = Path::new(path)
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect();
+    let mut templates = stack.templates();
+    if matches!(stack, Stack::Django { .. }) && django_settings_package(base).as_deref() != Some("project") {
+        // django-admin wrote manage.py and the settings package itself.
+        templates.retain(|(relative, _)| *relative != "manage.py" && !relative.starts_with("project/"));
+    }
+    let Some((relative, template)) = templates.iter().find(|(relative, _)| Path::new(relative) == wanted) else {
+        let known: Vec<&str> = templates.iter().map(|(relative, _)| *relative).collect();
+        return Err(anyhow!(
+            "{} projects have no built-in template for '{}'. Files with templates: {}",
+            stack.name(),
+            path,
+            if known.is_empty() { "none".to_string() } else { known.join(", ") }
+        ));
+    };
+
+    let target = base.join(relative);
+    let port = port
+        .or_else(|| existing_port(template, &fs::read_to_string(&target).ok()?))
+        .or(stack.default_port())
+        .unwrap_or_default();
+    let mut content = with_port(template, port);
+    if content.contains("{name}") {
+        // `project` may be `.`; the name comes from the directory it stands for.
+        let dir = fs::canonicalize(base).unwrap_or_else(|_| base.to_path_buf());
+        let name = package_name(&dir.to_string_lossy(), naming::to_kebab)?;
+        content = content.replace("{name}", &name);
+    }
+    if target.exists()
+        && !prompt_yes_no(&format!("Overwrite {:?} with the default template?", target), no_prompt)
+    {
+        say!("Left {:?} unchanged.", target);
+        return Ok(());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| anyhow!("Failed to create directory {:?}: {}", parent, e))?;
+    }
+    write_template(&target, &content)?;
+    say!("Reset {:?} to the built-in {} template.", target, stack.name());
+    Ok(())
+}
+
+/// Derives a package or module name from the last component of `project`
+/// with `convert`, leaving the directory itself as the user typed it.
+fn package_name(project: &str, convert: fn(&str) -> String) -> Result<String> {
+    let display = Path::new(project)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(project);
+    let name = convert(display);
+    if name.is_empty() {
+        return Err(anyhow!("Cannot derive a package name from '{}'; use letters or digits.", display));
+    }
+    if name != display {
+        say!("Using package name '{}' for '{}'.", name, display);
+    }
+    Ok(name)
+}
+
+/// Prints what a run is about to do: stack, target, extras and the tools it
+/// will invoke, followed by warnings about flags that will have no effect.
+fn describe_plan(
+    ctx: &mut GenContext,
+    cli: &Cli,
+    stack: &Stack,
+    project: &str,
+    extras: &[&str],
+    venv_dir: Option<&Path>,
+    port: Option<u16>,
+) {
+    let target = std::path::absolute(project).unwrap_or_else(|_| PathBuf::from(project));
+    say!("Plan:");
+    say!("  stack:   {}", stack.name());
+    say!("  target:  {}", target.display());
+    say!("  extras:  {}", if extras.is_empty() { "none".to_string() } else { extras.join(", ") });
+    if let (true, Some(file_name)) = (cli.manifest_only, stack.manifest_file_name()) {
+        say!("  writes:  {} only", file_name);
+    }
+    if let Some(venv_dir) = venv_dir {
+        say!("  venv:    {}", venv_dir.display());
+    }
+    if let Some(port) = port {
+        say!("  port:    {}", port);
+    }
+
+    let mut requirements = if cli.manifest_only { Vec::new() } else { stack.requirements() };
+    for (needed, name) in [
+        (cli.git && !cli.manifest_only, "git"),
+        (extras.contains(&"pre-commit") && cli.install, "pre-commit"),
+    ] {
+        if needed {
+            requirements.push(ToolRequirement {
+                names: vec![name],
+                has_fallback: false,
+            });
+        }
+    }
+    for requirement in &requirements {
+        match requirement.names.iter().find(|name| command_exists(name)) {
+            Some(found) => {
+                let path = tool_override(found)
+                    .map(Path::to_path_buf)
+                    .or_else(|| which(found).ok())
+                    .unwrap_or_else(|| PathBuf::from(found));
+                say!("  tool:    {} ({})", found, path.display());
+            }
+            None if requirement.has_fallback => {
+                say!("  tool:    {} not found; the basic fallback scaffold is used", requirement.names.join(" or "))
+            }
+            None => say!("  tool:    {} not found", requirement.names.join(" or ")),
+        }
+    }
+
+    // Worded like the warnings the run itself gives, so they are not repeated;
+    // `--port` was already warned about.
+    if cli.install && venv_dir.is_none() && !extras.contains(&"pre-commit") {
+        warning!(ctx, "--install does nothing for {} projects without --pre-commit.", stack.name());
+    }
+    if cli.openapi && stack.api_paths().is_empty() {
+        warning!(ctx, "{} projects serve no HTTP endpoints; skipping openapi.yaml.", stack.name());
+    }
+    if cli.venv_path.is_some() && venv_dir.is_none() {
+        warning!(ctx, "{} projects have no virtual environment; ignoring --venv-path.", stack.name());
+    }
+    if extras.contains(&"pre-commit") && cli.install && !cli.git && !Path::new(project).join(".git").exists() {
+        warning!(ctx, "{:?} is not a git repository; pass --git, or run 'pre-commit install' after 'git init'.", Path::new(project));
+    }
+    if cli.check_updates && stack.pinned_dependencies().is_empty() {
+        warning!(ctx, "The {} templates pin no crate versions; --check-updates has nothing to check.", stack.name());
+    }
+    if cli.seed.is_some() {
+        warning!(ctx, "The secrets in .env come from --seed and are predictable; never deploy them.");
+    }
+}
+
+/// `project` with the first numeric suffix (`-1`, `-2`, ...) that names a
+/// path which does not exist yet.
+fn free_project_name(project: &str) -> Result<String> {
+    let trimmed = project.trim_end_matches(['/', '\\']);
+    (1..=1000)
+        .map(|n| format!("{}-{}", trimmed, n))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .ok_or_else(|| anyhow!("Could not find a free name for {:?}.", project))
+}
+
+/// Warns when the project name collides with a command on PATH or a word the
+/// stack reserves. Advisory only, unless `strict` turns it into an error.
+fn check_project_name(ctx: &mut GenContext, stack: &Stack, project: &str, strict: bool) -> Result<()> {
+    let name = Path::new(project)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(project);
+    let mut problems = Vec::new();
+    if command_exists(name) {
+        problems.push(format!("'{}' is also a command on your PATH", name));
+    }
+    if stack.reserved_names().iter().any(|reserved| reserved.eq_ignore_ascii_case(name)) {
+        problems.push(format!("'{}' is a reserved name for {} projects", name, stack.name()));
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    if strict {
+        return Err(anyhow!("{}. Please choose another project name.", problems.join("; ")));
+    }
+    for problem in &problems {
+        warning!(ctx, "{}. Consider renaming the project.", problem);
+    }
+    Ok(())
+}
+
+/// Runs every precheck for generating `project` without touching the
+/// filesystem, prints the plan and returns the exit code for the verdict.
+fn dry_run(cli: &Cli, stack: &Stack, project: &str) -> i32 {
+    match stack.manifest_file_name() {
+        Some(file_name) if cli.manifest_only => {
+            say!("Dry run: would write the {} of a {} project in {:?}", file_name, stack.name(), project)
+        }
+        _ => say!("Dry run: would create a {} project in {:?}", stack.name(), project),
+    }
+    // `--manifest-only` writes one file itself, without any tool.
+    let mut requirements = if cli.manifest_only { Vec::new() } else { stack.requirements() };
+    if cli.git && !cli.manifest_only {
+        requirements.push(ToolRequirement {
+            names: vec!["git"],
+            has_fallback: false,
+        });
+    }
+
+    let invalid = stack.precheck(project).err().map(|e| e.to_string()).or_else(|| {
+        (cli.manifest_only && stack.manifest_file_name().is_none())
+            .then(|| format!("{} projects have no package manifest to write", stack.name()))
+    });
+    match &invalid {
+        Some(e) => say!("  [invalid]  {}", e),
+        None => say!("  [ok]       options and names"),
+    }
+
+    let mut missing_tool = false;
+    for requirement in &requirements {
+        let label = requirement.names.join(" or ");
+        match requirement.names.iter().find(|name| command_exists(name)) {
+            Some(found) => say!("  [ok]       {}", found),
+            None if requirement.has_fallback => {
+                say!("  [fallback] {} not found; a basic scaffold would be created", label)
+            }
+            None => {
+                say!("  [missing]  {}", label);
+                missing_tool = true;
+            }
+        }
+    }
+
+    let target = Path::new(project);
+    let replaced = cli.force && target.exists() && dangling_link(target).is_none();
+    let target_problem = if cli.on_conflict == Some(OnConflict::Error) && fs::symlink_metadata(target).is_ok() {
+        Some(format!("target {:?} already exists, and --on-conflict error refuses it", target))
+    } else if let (true, Some(file_name)) = (cli.manifest_only, stack.manifest_file_name()) {
+        let manifest = target.join(file_name);
+        if let Some(link) = dangling_link(target) {
+            Some(format!("{:?} is a symlink to a missing path", link))
+        } else if manifest.exists() {
+            Some(format!("{:?} already exists; it would not be overwritten", manifest))
+        } else {
+            None
+        }
+    } else if replaced {
+        replacement_target(target).err().map(|e| e.to_string())
+    } else {
+        check_target(target, stack.needs_fresh_target())
+    };
+    match &target_problem {
+        Some(problem) => say!("  [conflict] {}", problem),
+        None if cli.manifest_only => say!("  [ok]       no package manifest in {:?} yet", project),
+        None if replaced => say!("  [ok]       target {:?} exists and would be replaced", project),
+        None => say!("  [ok]       target {:?} is free", project),
+    }
+
+    if invalid.is_some() {
+        say!("Dry run failed: the generator would reject the options.");
+        EXIT_INVALID_OPTIONS
+    } else if missing_tool {
+        say!("Dry run failed: a required tool is missing.");
+        EXIT_MISSING_TOOL
+    } else if target_problem.is_some() {
+        say!("Dry run failed: the target directory cannot be used.");
+        EXIT_TARGET_CONFLICT
+    } else {
+        say!("Dry run passed: the project can be generated.");
+        EXIT_PLAN_OK
+    }
+}
+
+/// Describes why `target` cannot be generated into, if it cannot.
+/// Symlinks are resolved for these checks, while files are later created
+/// through the path as given; a dangling link anywhere on it is a conflict.
+/// With `fresh`, any existing target is one, even an empty directory.
+fn check_target(target: &Path, fresh: bool) -> Option<String> {
+    if let Some(link) = dangling_link(target) {
+        return Some(format!("{:?} is a symlink to a missing path", link));
+    }
+    if let Some(problem) = target_conflict(target, fresh) {
+        return Some(problem);
+    }
+    // Missing parents are created along with the project, so what matters is
+    // the closest one that exists.
+    let parent = target
+        .ancestors()
+        .skip(1)
+        .map(|ancestor| if ancestor.as_os_str().is_empty() { Path::new(".") } else { ancestor })
+        .find(|ancestor| ancestor.exists())?;
+    match fs::metadata(parent) {
+        Ok(metadata) if !metadata.is_dir() => Some(format!("{:?} is not a directory", parent)),
+        Ok(metadata) if metadata.permissions().readonly() => {
+            Some(format!("{:?} is not writable", parent))
+        }
+        Ok(_) => None,
+        Err(e) => Some(format!("{:?} cannot be inspected: {}", parent, e)),
+    }
+}
+
+/// Describes what is already at `target`, if generating into it would clash:
+/// anything at all when `fresh`, otherwise what [`is_occupied`] reports.
+fn target_conflict(target: &Path, fresh: bool) -> Option<String> {
+    if is_occupied(target) {
+        Some(format!("target {:?} already exists and is not empty", target))
+    } else if fresh && target.exists() {
+        Some(format!("target {:?} already exists, and the stack's generator refuses any existing destination", target))
+    } else {
+        None
+    }
+}
+
+/// Whether `target` holds something generating into it would overwrite:
+/// a file, or a directory with any entries.
+fn is_occupied(target: &Path) -> bool {
+    match fs::metadata(target) {
+        Ok(metadata) if metadata.is_dir() => {
+            fs::read_dir(target).map_or(true, |mut entries| entries.next().is_some())
+        }
+        Ok(_) => true,
+        Err(_) => false,
+    }
+}
+
+/// The path `--force` replaces for the existing `target`. A symlink is kept
+/// and what it points to is replaced, since files are created through it.
+/// The current directory and its parents are refused.
+fn replacement_target(target: &Path) -> Result<PathBuf> {
+    let resolved = fs::canonicalize(target)
+        .map_err(|e| anyhow!("Failed to resolve target {:?}: {}", target, e))?;
+    let current = std::env::current_dir()
+        .and_then(fs::canonicalize)
+        .map_err(|e| anyhow!("Failed to resolve the current directory: {}", e))?;
+    if current.starts_with(&resolved) {
+        return Err(anyhow!(
+            "Refusing to replace {:?}: it is the current directory or one of its parents.",
+            target
+        ));
+    }
+    let is_link = fs::symlink_metadata(target).is_ok_and(|metadata| metadata.file_type().is_symlink());
+    Ok(if is_link { resolved } else { target.to_path_buf() })
+}
+
+/// The first component of `path` that is a symlink whose target is missing.
+fn dangling_link(path: &Path) -> Option<&Path> {
+    let mut ancestors: Vec<&Path> = path
+        .ancestors()
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .collect();
+    ancestors.reverse();
+    ancestors.into_iter().find(|ancestor| {
+        fs::symlink_metadata(ancestor).is_ok_and(|metadata| metadata.file_type().is_symlink())
+            && !ancestor.exists()
+    })
+}
+
+/// The manifests decide which subcommands exist, so `--template-dir` has to
+/// be read before clap parses the real command line.
+fn template_dir_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--template-dir" {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix("--template-dir=") {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// The manifest directory in the user's config directory: under
+/// `$XDG_CONFIG_HOME` or `~/.config`, or `%APPDATA%` on Windows. The working
+/// directory is never scanned, since generated projects have `templates/`
+/// directories of their own.
+fn user_template_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    let config = if cfg!(windows) {
+        var("APPDATA")?
+    } else {
+        var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))?
+    };
+    Some(config.join(USER_TEMPLATE_DIR))
+}
+
+fn command_exists(command: &str) -> bool {
+    tool_override(command).is_some() || which(command).is_ok()
+}
+
+/// Where a command was looked for, for `--verbose` explanations.
+struct ToolSearch {
+    /// `PATH` entries in search order, with whether each one exists.
+    path_dirs: Vec<(PathBuf, bool)>,
+    /// Executable names tried in each directory (several on Windows).
+    candidates: Vec<String>,
+    found: Option<PathBuf>,
+}
+
+fn search_tool(command: &str) -> ToolSearch {
+    let path_dirs: Vec<(PathBuf, bool)> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dir| {
+            let exists = dir.is_dir();
+            (dir, exists)
+        })
+        .collect();
+    let candidates = if cfg!(windows) {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+        let mut names = vec![command.to_string()];
+        names.extend(pathext.split(';').filter(|ext| !ext.is_empty()).map(|ext| format!("{}{}", command, ext)));
+        names
+    } else {
+        vec![command.to_string()]
+    };
+    ToolSearch {
+        path_dirs,
+        candidates,
+        found: which(command).ok(),
+    }
+}
+
+/// Directories tools are commonly installed to that a shell may not have on PATH.
+fn common_tool_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["/usr/local/bin", "/opt/homebrew/bin", "/snap/bin"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        let home = PathBuf::from(home);
+        for relative in [".cargo/bin", ".local/bin", ".symfony5/bin", ".pyenv/shims", ".npm-global/bin"] {
+            dirs.push(home.join(relative));
+        }
+    }
+    dirs
+}
+
+/// Under `--verbose`, explains why none of `commands` was found: which PATH
+/// entries were searched for which names, and any copy installed in a
+/// directory the current PATH leaves out.
+fn explain_missing(commands: &[&str]) {
+    if !VERBOSE.load(Ordering::Relaxed) {
+        return;
+    }
+    for command in commands {
+        let search = search_tool(command);
+        if let Some(found) = &search.found {
+            say!("  '{}' resolves to {}", command, found.display());
+            continue;
+        }
+        say!(
+            "  Looked for {} in {} PATH entries:",
+            search.candidates.join(", "),
+            search.path_dirs.len()
+        );
+        for (dir, exists) in &search.path_dirs {
+            say!("    {}{}", dir.display(), if *exists { "" } else { " (does not exist)" });
+        }
+        let on_path = |dir: &Path| search.path_dirs.iter().any(|(path_dir, _)| path_dir == dir);
+        for dir in common_tool_dirs().iter().filter(|dir| !on_path(dir)) {
+            if let Some(name) = search.candidates.iter().find(|name| dir.join(name).is_file()) {
+                say!(
+                    "  Found {} but {} is not on PATH in this shell; add it to PATH.",
+                    dir.join(name).display(),
+                    dir.display()
+                );
+            }
+        }
+    }
+    if let Some(flag) = commands.iter().find_map(|command| override_flag(command)) {
+        say!("  Use {} <PATH> to point at an executable outside PATH.", flag);
+    }
+}
+
+/// The flag that points the tool at `command` outside PATH, if there is one.
+fn override_flag(command: &str) -> Option<&'static str> {
+    match command {
+        "symfony" => Some("--symfony-bin"),
+        "cargo" => Some("--cargo-bin"),
+        "python" | "python3" => Some("--python"),
+        _ => None,
+    }
+}
+
+fn tool_override(command: &str) -> Option<&'static Path> {
+    TOOL_OVERRIDES
+        .get()?
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, path)| path.as_path())
+}
+
+/// The executable to run for `command`: its override if one was given,
+/// else the bare name for the OS to look up on PATH.
+fn resolve_tool(command: &str) -> PathBuf {
+    tool_override(command)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(command))
+}
+
+/// `Command::new` for a tool the generators delegate to, honoring overrides
+/// and passing the proxy settings on.
+fn tool_command(command: &str) -> Command {
+    let mut command = Command::new(resolve_tool(command));
+    proxy::pass_to(&mut command);
+    command
+}
+
+fn check_executable(path: &Path) -> Result<()> {
+    let metadata = fs::metadata(path).map_err(|e| anyhow!("Cannot use {:?}: {}", path, e))?;
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let executable = true;
+    if !metadata.is_file() || !executable {
+        return Err(anyhow!("Cannot use {:?}: it is not an executable file.", path));
+    }
+    Ok(())
+}
+
+/// Runs `<tool> --version` and returns the first line it printed, or `None`
+/// if the tool could not be executed.
+fn probe_tool_version(tool: &str) -> Option<String> {
+    let output = tool_command(tool)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    // Some tools (older Pythons, for one) print their version to stderr.
+    let text = if output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    } else {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    Some(text.lines().next().unwrap_or("").trim().to_string())
 }
 
-#[derive(Subcommand)]
-enum Commands {
-    Symfony {
-        project: String,
-    },
-    Flask {
-        project: String,
-    },
-    Django {
-        project: String,
-    },
-    Rust {
-        project: String,
-    },
+fn run_doctor() {
+    say!("Checking tools used by the generators...");
+    let results: Mutex<Vec<Option<Option<String>>>> = Mutex::new(vec![None; DOCTOR_TOOLS.len()]);
+    let next = AtomicUsize::new(0);
+    let workers = MAX_PARALLEL_PROBES.min(DOCTOR_TOOLS.len());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(tool) = DOCTOR_TOOLS.get(index) else {
+                    break;
+                };
+                let version = if command_exists(tool) {
+                    probe_tool_version(tool)
+                } else {
+                    None
+                };
+                results.lock().unwrap()[index] = Some(version);
+            });
+        }
+    });
+
+    // Results are stored by tool index, so the table order never depends on
+    // which probe happened to finish first.
+    let results = results.into_inner().unwrap();
+    say!("{:<14} {:<8} Version", "Tool", "Status");
+    for (tool, result) in DOCTOR_TOOLS.iter().zip(results) {
+        match result.flatten() {
+            Some(version) => say!("{:<14} {:<8} {}", tool, "found", version),
+            None => say!("{:<14} {:<8} -", tool, "missing"),
+        }
+    }
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    match &cli.command {
-        Commands::Symfony { project } => create_symfony_project(project, cli.no_prompt)?,
-        Commands::Flask { project } => create_flask_project(project, cli.no_prompt)?,
-        Commands::Django { project } => create_django_project(project, cli.no_prompt)?,
-        Commands::Rust { project } => create_rust_project(project)?,
+/// Prints every stack with its aliases, tools and flags. Everything is read
+/// from [`Stack`] and the clap definition, so the report cannot drift from
+/// what the generators actually do.
+/// The global flags that do something for `stack`; flags that apply to every
+/// stack alike (`--git`, `--force`, ...) are left out.
+fn stack_global_flags(stack: &Stack) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    for requirement in stack.requirements() {
+        if let Some(flag) = requirement.names.iter().find_map(|name| override_flag(name)) {
+            if !flags.contains(&flag) {
+                flags.push(flag);
+            }
+        }
+    }
+    let serves = stack.default_port().is_some();
+    for (applies, names) in [
+        (!stack.gitignore_entries().is_empty(), &["--gitignore", "--no-gitignore"][..]),
+        (!stack.api_paths().is_empty(), &["--openapi", "--no-openapi"]),
+        (!stack.pre_commit_repos().is_empty(), &["--pre-commit", "--no-pre-commit"]),
+        (stack.uses_virtualenv(), &["--venv-path", "--venv"]),
+        (stack.uses_virtualenv() || !stack.pre_commit_repos().is_empty(), &["--install"]),
+        (serves, &["--port"]),
+        (stack.run_command(stack.default_port()).is_some(), &["--launcher"]),
+        (!stack.pinned_dependencies().is_empty(), &["--check-updates"]),
+        (stack.manifest_file_name().is_some(), &["--manifest-only"]),
+    ] {
+        if applies {
+            flags.extend_from_slice(names);
+        }
     }
+    flags
+}
+
+fn list_stacks(generators: &[manifest::ManifestGenerator], json: bool) {
+    let mut stacks = vec![
+        Stack::Symfony,
+        Stack::Flask,
+        Stack::Django { drf: false },
+        Stack::Worker,
+        Stack::Rust {
+            crate_type: CrateType::Bin,
+            framework: None,
+            bin_name: None,
+            features: &[],
+        },
+    ];
+    stacks.extend(generators.iter().map(Stack::Manifest));
+    let mut command = Cli::command();
+    for generator in generators {
+        command = command.subcommand(generator.subcommand());
+    }
+
+    let mut report = Vec::new();
+    for stack in &stacks {
+        let subcommand = command
+            .find_subcommand(stack.name())
+            .expect("every stack is a subcommand");
+        let aliases: Vec<&str> = subcommand.get_all_aliases().collect();
+        let mut flags: Vec<String> = stack_global_flags(stack).into_iter().map(String::from).collect();
+        flags.extend(
+            subcommand
+                .get_arguments()
+                .filter_map(|arg| arg.get_long())
+                .filter(|long| *long != "help")
+                .map(|long| format!("--{}", long)),
+        );
+        let tools: Vec<serde_json::Value> = stack
+            .requirements()
+            .iter()
+            .map(|requirement| {
+                serde_json::json!({
+                    "names": requirement.names,
+                    "present": requirement.names.iter().any(|name| command_exists(name)),
+                    "has_fallback": requirement.has_fallback,
+                })
+            })
+            .collect();
+        let ready = tools
+            .iter()
+            .all(|tool| tool["present"] == true || tool["has_fallback"] == true);
+        report.push(serde_json::json!({
+            "name": stack.name(),
+            "aliases": aliases,
+            "tools": tools,
+            "ready": ready,
+            "flags": flags,
+        }));
+    }
+
+    if json {
+        let text = serde_json::to_string_pretty(&report).expect("JSON values always serialize");
+        println!("{}", text);
+        return;
+    }
+    say!("{:<10} {:<6} Tools", "Stack", "Ready");
+    for entry in &report {
+        let tools: Vec<String> = entry["tools"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|tool| {
+                let names: Vec<&str> = tool["names"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|name| name.as_str())
+                    .collect();
+                let status = match (tool["present"] == true, tool["has_fallback"] == true) {
+                    (true, _) => "found",
+                    (false, true) => "fallback",
+                    (false, false) => "missing",
+                };
+                format!("{} ({})", names.join("/"), status)
+            })
+            .collect();
+        say!(
+            "{:<10} {:<6} {}",
+            entry["name"].as_str().unwrap_or(""),
+            if entry["ready"] == true { "yes" } else { "no" },
+            tools.join(", ")
+        );
+    }
+}
+
+fn init_git_repository(project: &str, message: &str) -> Result<()> {
+    if !command_exists("git") {
+        return Err(anyhow!("Git was not found on your system. Please install git to use --git."));
+    }
+    let base = Path::new(project);
+    // Some generators (cargo, symfony) already initialize a repository.
+    if !base.join(".git").exists() {
+        let status = tool_command("git")
+            .arg("init")
+            .current_dir(base)
+            .stdout(child_stdout())
+            .status()?;
+        if !status.success() {
+            return Err(anyhow!("Failed to initialize git repository in {:?}.", base));
+        }
+    }
+    let status = tool_command("git")
+        .args(["add", "-A"])
+        .current_dir(base)
+        .stdout(child_stdout())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to stage files in {:?}.", base));
+    }
+    let status = tool_command("git")
+        .args(["commit", "-m", message])
+        .current_dir(base)
+        .stdout(child_stdout())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to create the initial git commit in {:?}.", base));
+    }
+    say!("Git repository initialized with an initial commit.");
     Ok(())
 }
 
-fn command_exists(command: &str) -> bool {
-    which(command).is_ok()
+/// Writes a generated file, normalizing its line endings to `--line-ending`.
+/// Every generated file goes through here so none of them has to care.
+/// Indentation is left as it is; see [`write_template`] for that.
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    write_with_line_ending(path, content, LINE_ENDING.get().copied().unwrap_or(LineEnding::Lf))
+}
+
+/// Writes `content` with the given line ending regardless of
+/// `--line-ending`, for files whose interpreter dictates one.
+fn write_with_line_ending(path: &Path, content: &str, line_ending: LineEnding) -> Result<()> {
+    let crlf = match line_ending {
+        LineEnding::Lf => false,
+        LineEnding::Crlf => true,
+        LineEnding::Native => cfg!(windows),
+    };
+    let normalized = content.replace("\r\n", "\n");
+    let normalized = if crlf {
+        normalized.replace('\n', "\r\n")
+    } else {
+        normalized
+    };
+    fs::write(path, normalized).map_err(|e| anyhow!("Failed to create file {:?}: {}", path, e))
+}
+
+/// Writes one of the tool's own templates: like [`write_file`], but
+/// re-indented to what `.editorconfig` asks for. Content the tool did not
+/// author (piped in, downloaded or produced by another generator) goes
+/// through [`write_file`] as it is.
+fn write_template(path: &Path, content: &str) -> Result<()> {
+    match editorconfig::indent_for(path) {
+        Some(indent) => write_file(path, &editorconfig::reindent(content, indent)),
+        None => write_file(path, content),
+    }
+}
+
+/// Writes the committed `.env.example` documenting `keys` (name, placeholder)
+/// and a matching `.env` for the real values, and makes sure `.env` is ignored
+/// by git. Keys documented with [`SECRET_PLACEHOLDER`] get a generated secret
+/// in `.env`. An existing `.env` is left alone so re-runs never clobber secrets.
+fn write_env_pair(base: &Path, keys: &[(&str, &str)]) -> Result<()> {
+    let example: String = keys
+        .iter()
+        .map(|(key, placeholder)| format!("{}={}\n", key, placeholder))
+        .collect();
+    let example_path = base.join(".env.example");
+    write_file(&example_path, &example)?;
+    let env_path = base.join(".env");
+    if !env_path.exists() {
+        let env: String = keys
+            .iter()
+            .map(|(key, placeholder)| {
+                if *placeholder == SECRET_PLACEHOLDER {
+                    format!("{}={}\n", key, generate_secret())
+                } else {
+                    format!("{}={}\n", key, placeholder)
+                }
+            })
+            .collect();
+        write_file(&env_path, &env)?;
+    }
+
+    merge_gitignore(base, &[".env"])
+}
+
+/// `--manifest-only`: writes the stack's package manifest into `project`,
+/// which may already exist, and nothing else.
+fn write_package_manifest(stack: &Stack, project: &str) -> Result<()> {
+    let (file_name, content) = package_manifest(stack, project)?
+        .ok_or_else(|| anyhow!("{} projects have no package manifest to write.", stack.name()))?;
+    let base = Path::new(project);
+    let path = base.join(file_name);
+    if path.exists() {
+        return Err(anyhow!("{:?} already exists; not overwriting it.", path));
+    }
+    fs::create_dir_all(base).map_err(|e| anyhow!("Failed to create directory {:?}: {}", base, e))?;
+    write_file(&path, &content)?;
+    say!("Wrote {:?}.", path);
+    Ok(())
+}
+
+/// The package manifest a stack's project is built around, as (file name,
+/// content): name, version, author, description and dependencies.
+fn package_manifest(stack: &Stack, project: &str) -> Result<Option<(&'static str, String)>> {
+    let Some(file_name) = stack.manifest_file_name() else {
+        return Ok(None);
+    };
+    let display = Path::new(project)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(project);
+    let description = format!("{} {} project", display, stack.name());
+    let author = git_author();
+    let manifest = match stack {
+        Stack::Rust {
+            crate_type,
+            framework,
+            bin_name,
+            features,
+        } => {
+            let name = package_name(project, naming::to_kebab)?;
+            cargo::validate_crate_name(&name)?;
+            let mut doc = toml_edit::DocumentMut::new();
+            let package = cargo::table(&mut doc, "package");
+            package["name"] = toml_edit::value(name.as_str());
+            package["version"] = toml_edit::value("0.1.0");
+            package["edition"] = toml_edit::value("2021");
+            package["description"] = toml_edit::value(description.as_str());
+            if let Some(author) = &author {
+                package["authors"] = toml_edit::value(toml_edit::Array::from_iter([author.as_str()]));
+            }
+            if *crate_type == CrateType::ProcMacro {
+                cargo::table(&mut doc, "lib")["proc-macro"] = toml_edit::value(true);
+            }
+            if let Some(bin_name) = bin_name {
+                cargo::validate_bin_name(bin_name)?;
+                cargo::add_bin_target(&mut doc, bin_name, "src/main.rs");
+            }
+            cargo::table(&mut doc, "dependencies");
+            add_template_dependencies(&mut doc, template_dependencies(*crate_type, *framework), features);
+            doc.to_string()
+        }
+        Stack::Flask | Stack::Django { .. } => {
+            let dependencies = stack.python_dependencies();
+            let mut doc = toml_edit::DocumentMut::new();
+            let table = cargo::table(&mut doc, "project");
+            table["name"] = toml_edit::value(package_name(project, naming::to_kebab)?);
+            table["version"] = toml_edit::value("0.1.0");
+            table["description"] = toml_edit::value(description.as_str());
+            table["requires-python"] = toml_edit::value(">=3.9");
+            table["dependencies"] = toml_edit::value(toml_edit::Array::from_iter(dependencies.iter().copied()));
+            if let Some(author) = &author {
+                let mut entry = toml_edit::InlineTable::new();
+                let (name, email) = split_author(author);
+                entry.insert("name", name.into());
+                if let Some(email) = email {
+                    entry.insert("email", email.into());
+                }
+                table["authors"] = toml_edit::value(toml_edit::Array::from_iter([entry]));
+            }
+            doc.to_string()
+        }
+        Stack::Symfony => {
+            let mut manifest = serde_json::json!({
+                "name": format!("app/{}", package_name(project, naming::to_kebab)?),
+                "description": description,
+                "type": "project",
+                "require": {
+                    "php": ">=8.2",
+                    "symfony/dotenv": "^7.1",
+                    "symfony/framework-bundle": "^7.1",
+                    "symfony/runtime": "^7.1",
+                },
+                "config": {"allow-plugins": {"symfony/runtime": true}},
+            });
+            if let Some(author) = &author {
+                let (name, email) = split_author(author);
+                let mut entry = serde_json::json!({"name": name});
+                if let Some(email) = email {
+                    entry["email"] = serde_json::json!(email);
+                }
+                manifest["authors"] = serde_json::json!([entry]);
+            }
+            json_document(&manifest)
+        }
+        Stack::Worker => {
+            let mut manifest = serde_json::json!({
+                "name": package_name(project, naming::to_kebab)?,
+                "version": "0.1.0",
+                "description": description,
+                "private": true,
+                "scripts": {"dev": "wrangler dev", "deploy": "wrangler deploy"},
+                "devDependencies": {"wrangler": "^3"},
+            });
+            if let Some(author) = &author {
+                manifest["author"] = serde_json::json!(author);
+            }
+            json_document(&manifest)
+        }
+        Stack::Manifest(_) => return Ok(None),
+    };
+    Ok(Some((file_name, manifest)))
+}
+
+fn json_document(value: &serde_json::Value) -> String {
+    let mut text = serde_json::to_string_pretty(value).expect("JSON values always serialize");
+    text.push('\n');
+    text
+}
+
+/// `Name <email>` from the user's git configuration, the way cargo fills
+/// `authors`; `None` when git or the setting is missing.
+fn git_author() -> Option<String> {
+    let config = |key: &str| {
+        let output = tool_command("git")
+            .args(["config", "--get", key])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+    let name = config("user.name")?;
+    Some(match config("user.email") {
+        Some(email) => format!("{} <{}>", name, email),
+        None => name,
+    })
+}
+
+fn split_author(author: &str) -> (&str, Option<&str>) {
+    match author.split_once(" <") {
+        Some((name, email)) => (name, Some(email.trim_end_matches('>'))),
+        None => (author, None),
+    }
+}
+
+/// Writes `<base>/openapi.yaml` describing the stack's sample endpoints.
+fn write_openapi_spec(ctx: &mut GenContext, base: &Path, stack: &Stack, port: Option<u16>) -> Result<()> {
+    let paths = stack.api_paths();
+    if paths.is_empty() {
+        warning!(ctx, "{} projects serve no HTTP endpoints; skipping openapi.yaml.", stack.name());
+        return Ok(());
+    }
+    let title = base
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| stack.name().to_string());
+    // JSON strings are valid YAML scalars, which sidesteps YAML quoting rules.
+    let quote = |text: &str| serde_json::to_string(text).expect("strings always serialize");
+    let mut spec = format!(
+        "openapi: 3.0.3\ninfo:\n  title: {}\n  version: 0.1.0\n",
+        quote(&title)
+    );
+    if let Some(port) = port {
+        spec.push_str(&format!("servers:\n  - url: http://localhost:{}\n", port));
+    }
+    spec.push_str("paths:\n");
+    for api in paths {
+        spec.push_str(&format!("  {}:\n    get:\n", quote(api.path)));
+        spec.push_str(&format!("      summary: {}\n", quote(api.summary)));
+        spec.push_str("      responses:\n        '200':\n          description: OK\n");
+        spec.push_str(&format!("          content:\n            {}:\n", api.content_type));
+        let schema = if api.content_type == "application/json" { "object" } else { "string" };
+        spec.push_str(&format!("              schema:\n                type: {}\n", schema));
+    }
+    write_file(&base.join("openapi.yaml"), &spec)
+}
+
+/// Appends the `entries` missing from `<base>/.gitignore`, creating it if
+/// needed. Existing lines are kept as they are and nothing is duplicated, so
+/// this is safe to run on any project, including ones cargo already set up.
+fn merge_gitignore(base: &Path, entries: &[&str]) -> Result<()> {
+    let path = base.join(".gitignore");
+    let mut gitignore = fs::read_to_string(&path).unwrap_or_default();
+    let mut present: Vec<String> = gitignore.lines().map(|line| line.trim().to_string()).collect();
+    let mut added = false;
+    for entry in entries {
+        if present.iter().any(|line| line == entry) {
+            continue;
+        }
+        if !gitignore.is_empty() && !gitignore.ends_with('\n') {
+            gitignore.push('\n');
+        }
+        gitignore.push_str(entry);
+        gitignore.push('\n');
+        present.push(entry.to_string());
+        added = true;
+    }
+    if added {
+        write_file(&path, &gitignore)?;
+    }
+    Ok(())
+}
+
+/// Prompt theme matching the resolved color setting: colorful on capable
+/// terminals, plain text with `--no-color`, `NO_COLOR` or a dumb terminal.
+fn prompt_theme() -> Box<dyn Theme> {
+    if COLOR_ENABLED.load(Ordering::Relaxed) {
+        Box::new(ColorfulTheme::default())
+    } else {
+        Box::new(SimpleTheme)
+    }
 }
 
 fn prompt_yes_no(question: &str, no_prompt: bool) -> bool {
@@ -51,25 +2252,63 @@ fn prompt_yes_no(question: &str, no_prompt: bool) -> bool {
         return true;
     }
     use dialoguer::Confirm;
-    Confirm::new()
+    Confirm::with_theme(&*prompt_theme())
         .with_prompt(question)
         .default(true)
         .interact()
         .unwrap_or(false)
 }
 
-fn create_symfony_project(project: &str, no_prompt: bool) -> Result<()> {
-    println!("Creating Symfony PHP project for: {}", project);
+fn read_stdin_template(relative: &str, project: &str) -> Result<(PathBuf, String)> {
+    let Some(path) = manifest::substitute_path(relative, project) else {
+        return Err(anyhow!("The --stdin-template-path '{}' must stay inside the project.", relative));
+    };
+    let mut content = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+        .map_err(|e| anyhow!("Failed to read the template from stdin: {}", e))?;
+    if content.is_empty() {
+        return Err(anyhow!("--stdin-template was given but stdin is empty."));
+    }
+    Ok((path, content))
+}
+
+/// Lets the user tick the optional extras that fit `stack`; returns the
+/// flag names (without `--`) of the chosen ones.
+fn prompt_extras(stack: &Stack) -> Vec<&'static str> {
+    let mut extras = vec![
+        ("git", "Initialize a git repository (--git)"),
+        ("gitignore", "Add .gitignore entries (--gitignore)"),
+        ("pre-commit", "Write a pre-commit config (--pre-commit)"),
+    ];
+    if !stack.api_paths().is_empty() {
+        extras.push(("openapi", "Write an OpenAPI spec (--openapi)"));
+    }
+    if stack.uses_virtualenv() {
+        extras.push(("install", "Install the requirements into the venv (--install)"));
+    }
+    let labels: Vec<&str> = extras.iter().map(|(_, label)| *label).collect();
+    let chosen = dialoguer::MultiSelect::with_theme(&*prompt_theme())
+        .with_prompt("Optional extras (space to toggle, enter to confirm)")
+        .items(&labels)
+        .interact()
+        .unwrap_or_default();
+    chosen.into_iter().map(|index| extras[index].0).collect()
+}
+
+fn create_symfony_project(ctx: &mut GenContext, project: &str, no_prompt: bool) -> Result<()> {
+    say!("Creating Symfony PHP project for: {}", project);
     if command_exists("symfony") {
-        println!("Found Symfony CLI. Using 'symfony new'.");
-        let status = Command::new("symfony")
-            .args(&["new", project])
+        say!("Found Symfony CLI. Using 'symfony new'.");
+        let status = tool_command("symfony")
+            .args(["new", project])
+            .stdout(child_stdout())
             .status()?;
         if !status.success() {
             return Err(anyhow!("Failed to create Symfony project with Symfony CLI."));
         }
     } else {
-        println!("Symfony CLI not found.");
+        say!("Symfony CLI not found.");
+        explain_missing(&["symfony"]);
         if prompt_yes_no("Symfony CLI is missing. Create directory structure manually as fallback?", no_prompt) {
             let base = Path::new(project);
             let dirs = ["config", "public", "src", "templates", "var", "vendor"];
@@ -79,153 +2318,664 @@ fn create_symfony_project(project: &str, no_prompt: bool) -> Result<()> {
                     .map_err(|e| anyhow!("Failed to create directory {:?}: {}", path, e))?;
             }
             let index_path = base.join("public").join("index.php");
-            fs::write(&index_path, "<?php\n// Symfony front controller placeholder\n")
-                .map_err(|e| anyhow!("Failed to create file {:?}: {}", index_path, e))?;
-            println!("Fallback Symfony project structure created successfully!");
+            write_template(&index_path, templates::SYMFONY_INDEX_PHP)?;
+            write_env_pair(base, &[("APP_ENV", "dev"), ("APP_SECRET", SECRET_PLACEHOLDER)])?;
+            say!("Fallback Symfony project structure created successfully!");
+            warning!(ctx, "Symfony CLI not found; created the basic fallback structure instead of running 'symfony new'.");
+        } else {
+            say!("Please install the Symfony CLI from https://symfony.com/download and try again.");
+        }
+    }
+    Ok(())
+}
+
+fn create_worker_project(ctx: &mut GenContext, project: &str, no_prompt: bool, port: u16) -> Result<()> {
+    say!("Creating Cloudflare Workers project for: {}", project);
+    if command_exists("npm") {
+        say!("Found npm. Using 'npm create cloudflare'.");
+        let mut command = tool_command("npm");
+        if no_prompt {
+            // Lets npx fetch create-cloudflare without asking.
+            command.arg("--yes");
+        }
+        command.args(["create", "cloudflare@latest", "--", project]);
+        if no_prompt {
+            command.args(["--type=hello-world", "--lang=js", "--no-deploy", "--no-git"]);
+        }
+        let status = command.stdout(child_stdout()).status()?;
+        if !status.success() {
+            return Err(anyhow!("Failed to create Workers project with create-cloudflare."));
+        }
+        let wrangler_toml = Path::new(project).join("wrangler.toml");
+        match fs::read_to_string(&wrangler_toml) {
+            Ok(config) if !config.contains("[dev]") => {
+                write_file(&wrangler_toml, &format!("{}\n[dev]\nport = {}\n", config.trim_end(), port))?;
+            }
+            Ok(_) => {}
+            // Newer create-cloudflare releases write wrangler.jsonc instead.
+            Err(_) => warning!(
+                ctx,
+                "create-cloudflare wrote no wrangler.toml; set \"dev\": {{ \"port\": {} }} in its Wrangler config by hand.",
+                port
+            ),
+        }
+    } else {
+        say!("npm not found.");
+        explain_missing(&["npm"]);
+        if prompt_yes_no("npm is missing. Create a basic Worker scaffold manually as fallback?", no_prompt) {
+            let base = Path::new(project);
+            let src = base.join("src");
+            fs::create_dir_all(&src)
+                .map_err(|e| anyhow!("Failed to create directory {:?}: {}", src, e))?;
+            write_template(&src.join("index.js"), templates::WORKER_INDEX_JS)?;
+            let name = package_name(project, naming::to_kebab)?;
+            write_template(
+                &base.join("wrangler.toml"),
+                &with_port(templates::WRANGLER_TOML, port).replace("{name}", &name),
+            )?;
+            say!("Fallback Worker scaffold created successfully!");
+            warning!(ctx, "npm not found; created the basic fallback Worker instead of running create-cloudflare.");
         } else {
-            println!("Please install the Symfony CLI from https://symfony.com/download and try again.");
+            say!("Please install Node.js and npm from https://nodejs.org and try again.");
         }
     }
     Ok(())
 }
 
-fn create_flask_project(project: &str, no_prompt: bool) -> Result<()> {
-    println!("Creating Python Flask project for: {}", project);
+fn create_flask_project(ctx: &mut GenContext, project: &str, no_prompt: bool, port: u16) -> Result<()> {
+    say!("Creating Python Flask project for: {}", project);
     if !command_exists("python") && !command_exists("python3") {
-        println!("Python was not found on your system.");
+        say!("Python was not found on your system.");
+        warning!(ctx, "Python was not found; install it before running the Flask app.");
+        explain_missing(&["python", "python3"]);
         if !prompt_yes_no("Abort and install Python first?", no_prompt) {
             return Err(anyhow!("Python is required for Flask projects."));
         }
     }
     let base = Path::new(project);
-    let dirs = ["app", "venv", "static", "templates"];
+    let dirs = ["app", "static", "templates"];
     for dir in &dirs {
         let path = base.join(dir);
         fs::create_dir_all(&path)
             .map_err(|e| anyhow!("Failed to create directory {:?}: {}", path, e))?;
     }
     let app_file = base.join("app").join("app.py");
-    let app_content = r#"from flask import Flask
-
-app = Flask(__name__)
-
-@app.route('/')
-def hello():
-    return "Here we go again!"
-
-if __name__ == '__main__':
-    app.run(debug=True)
-"#;
-    fs::write(&app_file, app_content)
-        .map_err(|e| anyhow!("Failed to create file {:?}: {}", app_file, e))?;
-
-    println!("Setting up Python virtual environment (ensure Python is installed)...");
-    let venv_dir = base.join("venv");
-    let python_cmd = if command_exists("python") { "python" } else { "python3" };
-    let status = Command::new(python_cmd)
-        .args(&["-m", "venv", venv_dir.to_str().unwrap()])
-        .status();
-
-    match status {
-        Ok(s) if s.success() => println!("Virtual environment created successfully!"),
-        _ => {
-            println!("Failed to create virtual environment.");
-            if prompt_yes_no("Would you like to try again manually?", no_prompt) {
-                println!("Please create the virtual environment using 'python -m venv venv' in your project directory.");
-            }
-        }
-    }
-    println!("Flask project scaffold created successfully!");
+    write_template(&app_file, &with_port(templates::FLASK_APP_PY, port))?;
+    write_env_pair(
+        base,
+        &[
+            ("FLASK_APP", "app/app.py"),
+            ("FLASK_DEBUG", "1"),
+            ("SECRET_KEY", SECRET_PLACEHOLDER),
+        ],
+    )?;
+    write_requirements(base, &Stack::Flask)?;
+    say!("Flask project scaffold created successfully!");
     Ok(())
 }
 
-fn create_django_project(project: &str, no_prompt: bool) -> Result<()> {
-    println!("Creating Django project for: {}", project);
+fn create_django_project(ctx: &mut GenContext, project: &str, no_prompt: bool, drf: bool) -> Result<()> {
+    say!("Creating Django project for: {}", project);
+    let base = Path::new(project);
     if command_exists("django-admin") {
-        println!("Found django-admin. Using 'django-admin startproject'.");
-        let status = Command::new("django-admin")
-            .args(&["startproject", project, project])
+        say!("Found django-admin. Using 'django-admin startproject'.");
+        let module = package_name(project, naming::to_snake)?;
+        fs::create_dir_all(base)
+            .map_err(|e| anyhow!("Failed to create directory {:?}: {}", base, e))?;
+        let status = tool_command("django-admin")
+            .args(["startproject", module.as_str(), project])
+            .stdout(child_stdout())
             .status()?;
         if !status.success() {
             return Err(anyhow!("Failed to create Django project with django-admin."));
         }
+        let package = base.join(&module);
+        let settings_file = package.join("settings.py");
+        let mut settings = fs::read_to_string(&settings_file)
+            .map_err(|e| anyhow!("Failed to read file {:?}: {}", settings_file, e))?;
+        // startproject bakes in a random key of its own; read the seeded one from .env instead.
+        match with_env_secret_key(&settings) {
+            Some(updated) => settings = updated,
+            None => warning!(
+                ctx,
+                "SECRET_KEY not found in {:?}; set it from DJANGO_SECRET_KEY in .env by hand.",
+                settings_file
+            ),
+        }
+        if drf {
+            match with_rest_framework_app(&settings) {
+                Some(updated) => settings = updated,
+                None => warning!(
+                    ctx,
+                    "INSTALLED_APPS not found in {:?}; add 'rest_framework' to it by hand.",
+                    settings_file
+                ),
+            }
+        }
+        write_file(&settings_file, &settings)?;
+        if drf {
+            let urls_file = package.join("urls.py");
+            let mut urls = fs::read_to_string(&urls_file)
+                .map_err(|e| anyhow!("Failed to read file {:?}: {}", urls_file, e))?;
+            urls.push_str(templates::DJANGO_DRF_URLS_APPENDIX);
+            write_file(&urls_file, &urls)?;
+        }
     } else {
-        println!("django-admin not found.");
+        say!("django-admin not found.");
+        explain_missing(&["django-admin"]);
         if prompt_yes_no("django-admin is missing. Create basic scaffold manually as fallback?", no_prompt) {
-            let base = Path::new(project);
-            let dirs = ["project", "app", "venv"];
+            let dirs = ["project", "app"];
             for dir in &dirs {
                 let path = base.join(dir);
                 fs::create_dir_all(&path)
                     .map_err(|e| anyhow!("Failed to create directory {:?}: {}", path, e))?;
             }
             let manage_py = base.join("manage.py");
-            let manage_content = r#"#!/usr/bin/env python
-import os
-import sys
-
-if __name__ == '__main__':
-    os.environ.setdefault('DJANGO_SETTINGS_MODULE', 'project.settings')
-    try:
-        from django.core.management import execute_from_command_line
-    except ImportError as exc:
-        raise ImportError("Couldn't import Django.") from exc
-    execute_from_command_line(sys.argv)
-"#;
-            fs::write(&manage_py, manage_content)
-                .map_err(|e| anyhow!("Failed to create file {:?}: {}", manage_py, e))?;
+            write_template(&manage_py, templates::DJANGO_MANAGE_PY)?;
             let settings_file = base.join("project").join("settings.py");
-            let settings_content = r#"SECRET_KEY = 'your-secret-key'
-DEBUG = True
-ALLOWED_HOSTS = []
-INSTALLED_APPS = [
-    'django.contrib.admin',
-    'django.contrib.auth',
-    'django.contrib.contenttypes',
-    'django.contrib.sessions',
-    'django.contrib.messages',
-    'django.contrib.staticfiles',
-    'app',
-]
-MIDDLEWARE = [
-    'django.middleware.security.SecurityMiddleware',
-    'django.contrib.sessions.middleware.SessionMiddleware',
-    'django.middleware.common.CommonMiddleware',
-]
-ROOT_URLCONF = 'project.urls'
-"#;
-            fs::write(&settings_file, settings_content)
-                .map_err(|e| anyhow!("Failed to create file {:?}: {}", settings_file, e))?;
-            println!("Fallback Django scaffold created successfully!");
+            if drf {
+                write_template(&settings_file, &drf_settings_template())?;
+                write_template(&base.join("project").join("urls.py"), templates::DJANGO_DRF_URLS_PY)?;
+            } else {
+                write_template(&settings_file, templates::DJANGO_SETTINGS_PY)?;
+            }
+            say!("Fallback Django scaffold created successfully!");
+            warning!(ctx, "django-admin not found; created the basic fallback scaffold instead of running 'startproject'.");
+        } else {
+            say!("Please install Django (pip install Django) to use the standard generator.");
+        }
+    }
+    if base.is_dir() {
+        write_env_pair(base, &[("DJANGO_SECRET_KEY", SECRET_PLACEHOLDER), ("DJANGO_DEBUG", "1")])?;
+        write_requirements(base, &Stack::Django { drf })?;
+    }
+    if drf && base.is_dir() {
+        fs::create_dir_all(base.join("api"))
+            .map_err(|e| anyhow!("Failed to create directory {:?}: {}", base.join("api"), e))?;
+        for (path, content) in DRF_API_FILES {
+            write_template(&base.join(path), content)?;
+        }
+        say!("Django REST Framework API added under /api/.");
+    }
+    Ok(())
+}
+
+/// Writes the stack's `requirements.txt` for `--install`, one package per line.
+fn write_requirements(base: &Path, stack: &Stack) -> Result<()> {
+    let requirements: String = stack
+        .python_dependencies()
+        .iter()
+        .map(|package| format!("{}\n", package))
+        .collect();
+    write_file(&base.join("requirements.txt"), &requirements)
+}
+
+/// Fills the `{port}` placeholder of a server template.
+fn with_port(template: &str, port: u16) -> String {
+    template.replace("{port}", &port.to_string())
+}
+
+/// Adds `'rest_framework'` as the first entry of `INSTALLED_APPS` in a
+/// settings module, whichever quote style it was written with; `None` if it
+/// has no `INSTALLED_APPS` list.
+fn with_rest_framework_app(settings: &str) -> Option<String> {
+    let mut result = String::with_capacity(settings.len() + 24);
+    let mut inserted = false;
+    for line in settings.split_inclusive('\n') {
+        result.push_str(line);
+        if !inserted && line.trim_start().starts_with("INSTALLED_APPS = [") {
+            result.push_str("    'rest_framework',\n");
+            inserted = true;
+        }
+    }
+    inserted.then_some(result)
+}
+
+/// The built-in settings module with `rest_framework` installed.
+/// Replaces the `SECRET_KEY` assignment of a settings module with the
+/// built-in settings' preamble, which loads `.env` and reads the key from
+/// `DJANGO_SECRET_KEY`; `None` if it assigns no `SECRET_KEY`.
+fn with_env_secret_key(settings: &str) -> Option<String> {
+    let template = templates::DJANGO_SETTINGS_PY;
+    let key_line = template.find("SECRET_KEY").expect("the built-in settings set SECRET_KEY");
+    let end = template[key_line..].find('\n').map_or(template.len(), |end| key_line + end + 1);
+    let preamble = &template[..end];
+    let mut result = String::with_capacity(settings.len() + preamble.len());
+    let mut replaced = false;
+    for line in settings.split_inclusive('\n') {
+        if !replaced && line.starts_with("SECRET_KEY") {
+            result.push_str(preamble);
+            replaced = true;
         } else {
-            println!("Please install Django (pip install Django) to use the standard generator.");
+            result.push_str(line);
+        }
+    }
+    replaced.then_some(result)
+}
+
+fn drf_settings_template() -> String {
+    with_rest_framework_app(templates::DJANGO_SETTINGS_PY).expect("the built-in settings list INSTALLED_APPS")
+}
+
+/// Creates the virtual environment for the Python stacks. Failure is not
+/// fatal: the scaffold is still usable and the user is told how to finish by
+/// hand.
+fn create_virtualenv(ctx: &mut GenContext, venv_dir: &Path, policy: VenvPolicy, no_prompt: bool) {
+    // `python -m venv` over an old environment keeps its stale interpreter links.
+    if venv_dir.join("pyvenv.cfg").is_file() {
+        match policy {
+            VenvPolicy::Reuse => {
+                warning!(
+                    ctx,
+                    "Reusing the existing virtual environment in {:?}; pass --venv recreate to rebuild it.",
+                    venv_dir
+                );
+                say!("Activate it with: {}", activation_hint(venv_dir));
+                return;
+            }
+            VenvPolicy::Recreate => {
+                say!("Removing the existing virtual environment in {:?}...", venv_dir);
+                if let Err(e) = fs::remove_dir_all(venv_dir) {
+                    warning!(ctx, "Failed to remove the virtual environment in {:?}: {}", venv_dir, e);
+                    return;
+                }
+            }
         }
     }
-    println!("Setting up Python virtual environment (ensure Python is installed)...");
-    let venv_dir = Path::new(project).join("venv");
+    say!("Setting up Python virtual environment (ensure Python is installed)...");
     let python_cmd = if command_exists("python") { "python" } else { "python3" };
-    let status = Command::new(python_cmd)
-        .args(&["-m", "venv", venv_dir.to_str().unwrap()])
+    let status = tool_command(python_cmd)
+        .arg("-m")
+        .arg("venv")
+        .arg(venv_dir)
+        .stdout(child_stdout())
         .status();
+
     match status {
-        Ok(s) if s.success() => println!("Virtual environment created successfully!"),
-        _ => println!("Failed to create virtual environment. Please create it manually."),
+        Ok(s) if s.success() => {
+            say!("Virtual environment created successfully!");
+            say!("Activate it with: {}", activation_hint(venv_dir));
+        }
+        _ => {
+            say!("Failed to create virtual environment.");
+            warning!(ctx, "Failed to create the virtual environment in {:?}.", venv_dir);
+            if prompt_yes_no("Would you like to try again manually?", no_prompt) {
+                say!("Please create the virtual environment using 'python -m venv {}'.", venv_dir.display());
+            }
+        }
+    }
+}
+
+/// The `.gitignore` entry for a virtual environment inside `base`, anchored
+/// to the project root; `None` when it lives outside the project.
+fn venv_gitignore_entry(base: &Path, venv_dir: &Path) -> Option<String> {
+    let relative = path_inside(base, venv_dir)?;
+    let relative: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Some(format!("/{}/", relative.join("/")))
+}
+
+/// `path` relative to `base` if it lies strictly inside it, comparing the
+/// resolved paths so `./x`, symlinks and absolute spellings agree.
+fn path_inside(base: &Path, path: &Path) -> Option<PathBuf> {
+    let resolve = |path: &Path| fs::canonicalize(path).or_else(|_| std::path::absolute(path)).ok();
+    let relative = resolve(path)?.strip_prefix(resolve(base)?).ok()?.to_path_buf();
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+/// Writes `run.bat` on Windows or an executable `run.sh` elsewhere, which
+/// switches to the project directory, activates the virtual environment for
+/// Python stacks and runs the stack's run command.
+fn write_launcher(ctx: &mut GenContext, base: &Path, stack: &Stack, venv_dir: Option<&Path>, port: Option<u16>) -> Result<()> {
+    let Some(command) = stack.run_command(port) else {
+        warning!(ctx, "{} projects have no run command; skipping the launcher.", stack.name());
+        return Ok(());
+    };
+    // Inside the project the launcher refers to the venv relatively, so the
+    // project can be moved.
+    let venv = venv_dir.map(|venv_dir| match path_inside(base, venv_dir) {
+        Some(relative) => relative,
+        None => std::path::absolute(venv_dir).unwrap_or_else(|_| venv_dir.to_path_buf()),
+    });
+    if cfg!(windows) {
+        let mut script = String::from("@echo off\ncd /d \"%~dp0\"\n");
+        if let Some(venv) = &venv {
+            script.push_str(&format!("call \"{}\"\n", venv.join("Scripts").join("activate.bat").display()));
+        }
+        script.push_str(&format!("{}\nif errorlevel 1 pause\n", command));
+        // cmd.exe mis-parses labels and blocks in LF-only batch files.
+        write_with_line_ending(&base.join("run.bat"), &script, LineEnding::Crlf)?;
+        say!("Launcher written to {:?}.", base.join("run.bat"));
+    } else {
+        let mut script = String::from("#!/bin/sh\nset -e\ncd \"$(dirname \"$0\")\"\n");
+        if let Some(venv) = &venv {
+            script.push_str(&format!(". \"{}\"\n", venv.join("bin").join("activate").display()));
+        }
+        script.push_str(&format!("exec {}\n", command));
+        let path = base.join("run.sh");
+        // A CR after the shebang breaks it, whatever --line-ending says.
+        write_with_line_ending(&path, &script, LineEnding::Lf)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| anyhow!("Failed to make {:?} executable: {}", path, e))?;
+        }
+        say!("Launcher written to {:?}.", path);
+    }
+    Ok(())
+}
+
+/// Runs the virtual environment's pip on `<base>/requirements.txt`; without
+/// one this only warns.
+fn install_requirements(ctx: &mut GenContext, base: &Path, venv_dir: &Path) -> Result<()> {
+    let requirements = base.join("requirements.txt");
+    if !requirements.is_file() {
+        warning!(ctx, "No requirements.txt in {:?}; skipping the install step.", base);
+        return Ok(());
+    }
+    let pip = if cfg!(windows) {
+        venv_dir.join("Scripts").join("pip.exe")
+    } else {
+        venv_dir.join("bin").join("pip")
+    };
+    if !pip.exists() {
+        warning!(ctx, "No pip found in {:?}; skipping the install step.", venv_dir);
+        return Ok(());
+    }
+    say!("Installing Python requirements...");
+    let mut command = Command::new(&pip);
+    proxy::pass_to(&mut command);
+    let status = command
+        .arg("install")
+        .arg("-r")
+        .arg(&requirements)
+        .stdout(child_stdout())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to install the requirements from {:?}.", requirements));
+    }
+    Ok(())
+}
+
+/// Runs `pre-commit install` so the hooks fire on the first commit. Without
+/// `pre-commit` on PATH or a git repository this only warns.
+fn install_pre_commit_hooks(ctx: &mut GenContext, base: &Path) -> Result<()> {
+    if !command_exists("pre-commit") {
+        warning!(ctx, "pre-commit was not found; run 'pre-commit install' once it is installed.");
+        return Ok(());
+    }
+    if !base.join(".git").exists() {
+        warning!(ctx, "{:?} is not a git repository; pass --git, or run 'pre-commit install' after 'git init'.", base);
+        return Ok(());
     }
+    let status = tool_command("pre-commit")
+        .arg("install")
+        .current_dir(base)
+        .stdout(child_stdout())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to install the pre-commit hooks in {:?}.", base));
+    }
+    say!("pre-commit hooks installed.");
     Ok(())
 }
 
-fn create_rust_project(project: &str) -> Result<()> {
-    println!("Creating Rust project for: {}", project);
+fn activation_hint(venv_dir: &Path) -> String {
+    if cfg!(windows) {
+        format!("{}", venv_dir.join("Scripts").join("activate").display())
+    } else {
+        format!("source {}", venv_dir.join("bin").join("activate").display())
+    }
+}
+
+fn create_rust_project(
+    ctx: &mut GenContext,
+    project: &str,
+    crate_type: CrateType,
+    framework: Option<RustFramework>,
+    bin_name: Option<&str>,
+    features: &[cargo::CrateFeatures],
+    port: Option<u16>,
+) -> Result<()> {
+    say!("Creating Rust project for: {}", project);
     if !command_exists("cargo") {
+        explain_missing(&["cargo"]);
         return Err(anyhow!("Cargo was not found on your system. Please install Rust (and Cargo) from https://rustup.rs."));
     }
-    let status = Command::new("cargo")
-        .args(&["new", project])
+    let base = Path::new(project);
+    let crate_name = check_rust_options(project, crate_type, framework, bin_name, features)?;
+    let kind = match crate_type {
+        CrateType::Bin => "--bin",
+        CrateType::Lib | CrateType::ProcMacro => "--lib",
+    };
+    let status = tool_command("cargo")
+        .args(["new", kind, "--name", &crate_name, project])
+        .stdout(child_stdout())
         .status()?;
-    if status.success() {
-        println!("Rust project created successfully!");
-    } else {
+    if !status.success() {
         return Err(anyhow!("Failed to create Rust project using Cargo."));
     }
+    if crate_type == CrateType::ProcMacro {
+        configure_proc_macro_crate(base, features)?;
+    }
+    if framework == Some(RustFramework::Yew) {
+        configure_yew_app(ctx, base, port.unwrap_or_default(), features)?;
+    }
+    if let Some(bin_name) = bin_name {
+        add_named_binary(base, bin_name)?;
+    }
+    say!("Rust project created successfully!");
+    Ok(())
+}
+
+/// Validates the Rust options against the template and returns the crate
+/// name derived from `project`. Shared by generation and `--dry-run`.
+fn check_rust_options(
+    project: &str,
+    crate_type: CrateType,
+    framework: Option<RustFramework>,
+    bin_name: Option<&str>,
+    features: &[cargo::CrateFeatures],
+) -> Result<String> {
+    if framework == Some(RustFramework::Yew) && crate_type != CrateType::Bin {
+        return Err(anyhow!("The yew framework is built by trunk and needs --crate-type bin."));
+    }
+    let crate_name = package_name(project, naming::to_kebab)?;
+    cargo::validate_crate_name(&crate_name)?;
+    if let Some(bin_name) = bin_name {
+        cargo::validate_bin_name(bin_name)?;
+    }
+    let dependencies = template_dependencies(crate_type, framework);
+    if let Some(unknown) = features
+        .iter()
+        .find(|requested| !dependencies.iter().any(|(name, _, _)| *name == requested.name))
+    {
+        let known: Vec<&str> = dependencies.iter().map(|(name, _, _)| *name).collect();
+        return Err(anyhow!(
+            "--rust-features names '{}', which this template does not add. Template dependencies: {}",
+            unknown.name,
+            if known.is_empty() { "none".to_string() } else { known.join(", ") }
+        ));
+    }
+    Ok(crate_name)
+}
+
+fn configure_proc_macro_crate(base: &Path, features: &[cargo::CrateFeatures]) -> Result<()> {
+    cargo::edit_manifest(base, |doc| {
+        cargo::table(doc, "lib")["proc-macro"] = toml_edit::value(true);
+        add_template_dependencies(doc, PROC_MACRO_DEPENDENCIES, features);
+    })?;
+    let lib_rs = base.join("src").join("lib.rs");
+    write_template(&lib_rs, templates::PROC_MACRO_LIB_RS)?;
+    Ok(())
+}
+
+/// Declares a `[[bin]]` target so the binary can be named independently of
+/// the package. Library crates get a `src/main.rs` for it to build from.
+fn add_named_binary(base: &Path, bin_name: &str) -> Result<()> {
+    let main_rs = base.join("src").join("main.rs");
+    if !main_rs.exists() {
+        write_template(&main_rs, templates::RUST_MAIN_RS)?;
+    }
+    cargo::edit_manifest(base, |doc| cargo::add_bin_target(doc, bin_name, "src/main.rs"))?;
+    say!("Binary target '{}' added.", bin_name);
+    Ok(())
+}
+
+/// Adds `dependencies` with their default features plus any requested with
+/// `--rust-features`.
+fn add_template_dependencies(
+    doc: &mut toml_edit::DocumentMut,
+    dependencies: &[CrateDependency],
+    requested: &[cargo::CrateFeatures],
+) {
+    for (name, version, defaults) in dependencies {
+        let mut features: Vec<&str> = defaults.to_vec();
+        for extra in requested.iter().filter(|extra| extra.name == *name) {
+            for feature in &extra.features {
+                if !features.contains(&feature.as_str()) {
+                    features.push(feature);
+                }
+            }
+        }
+        cargo::add_dependency(doc, name, version, &features);
+    }
+}
+
+fn configure_yew_app(ctx: &mut GenContext, base: &Path, port: u16, features: &[cargo::CrateFeatures]) -> Result<()> {
+    cargo::edit_manifest(base, |doc| add_template_dependencies(doc, YEW_DEPENDENCIES, features))?;
+    write_template(&base.join("src").join("main.rs"), templates::YEW_MAIN_RS)?;
+    write_template(&base.join("index.html"), templates::YEW_INDEX_HTML)?;
+    write_template(&base.join("Trunk.toml"), &with_port(templates::TRUNK_TOML, port))?;
+    if !command_exists("trunk") && !command_exists("wasm-pack") {
+        warning!(ctx, "Neither trunk nor wasm-pack was found. Install trunk with 'cargo install trunk' and add the target with 'rustup target add wasm32-unknown-unknown'.");
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_mode_fails_on_the_first_warning() {
+        let mut ctx = GenContext::default();
+        assert!(ctx.strict_failure().is_none());
+        warning!(ctx, "first {}", 1);
+        warning!(ctx, "second");
+        warning!(ctx, "second");
+        assert!(ctx.strict_failure().is_none());
+        ctx.strict = true;
+        let error = ctx.strict_failure().expect("warnings fail a strict run").to_string();
+        assert!(error.contains("2 warning(s)") && error.ends_with("first: first 1"), "{}", error);
+    }
+
+    #[test]
+    fn startproject_settings_read_the_secret_key_from_the_environment() {
+        let settings = "from pathlib import Path\n\nSECRET_KEY = 'django-insecure-abc'\nDEBUG = True\n";
+        let updated = with_env_secret_key(settings).expect("the settings assign SECRET_KEY");
+        assert!(!updated.contains("django-insecure"), "{}", updated);
+        assert!(updated.contains("SECRET_KEY = os.environ['DJANGO_SECRET_KEY']\nDEBUG = True\n"), "{}", updated);
+        assert!(updated.contains("/ '.env'"), "{}", updated);
+        assert_eq!(with_env_secret_key("DEBUG = True\n"), None);
+    }
+
+    #[test]
+    fn seeded_secrets_are_reproducible() {
+        assert_eq!(SECRET_ALPHABET.len(), 64);
+        let secret = secret_from(&mut ChaCha20Rng::seed_from_u64(42));
+        assert_eq!(secret, secret_from(&mut ChaCha20Rng::seed_from_u64(42)));
+        assert_ne!(secret, secret_from(&mut ChaCha20Rng::seed_from_u64(43)));
+        // Pinned so a dependency bump cannot silently change seeded output.
+        assert_eq!(secret, "4rz5vDyOvNQefK0nIjVipYKhYpes_GeXdzKZs3JFNwkOjSaBMW");
+    }
+
+    #[test]
+    fn reads_the_port_back_from_a_generated_file() {
+        let generated = with_port(templates::FLASK_APP_PY, 9000);
+        assert_eq!(existing_port(templates::FLASK_APP_PY, &generated), Some(9000));
+        let trunk = with_port(templates::TRUNK_TOML, 8123);
+        assert_eq!(existing_port(templates::TRUNK_TOML, &trunk), Some(8123));
+        assert_eq!(existing_port(templates::FLASK_APP_PY, "print('no server')\n"), None);
+        assert_eq!(existing_port(templates::WORKER_INDEX_JS, &generated), None);
+    }
+
+    #[test]
+    fn finds_the_settings_package_of_both_django_layouts() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path();
+        for (project, package) in [("fallback", "project"), ("startproject", "my_site")] {
+            let base = dir.join(project);
+            fs::create_dir_all(base.join(package)).unwrap();
+            fs::write(base.join(package).join("settings.py"), "").unwrap();
+            let manage_py = templates::DJANGO_MANAGE_PY.replace("project.settings", &format!("{}.settings", package));
+            fs::write(base.join("manage.py"), manage_py).unwrap();
+            assert_eq!(django_settings_package(&base).as_deref(), Some(package));
+            assert!(matches!(detect_stack(&base), Some(Stack::Django { drf: false })));
+        }
+        fs::remove_file(dir.join("startproject").join("my_site").join("settings.py")).unwrap();
+        assert_eq!(django_settings_package(&dir.join("startproject")), None);
+    }
+
+    #[test]
+    fn an_empty_target_only_conflicts_when_the_generator_needs_a_fresh_one() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path();
+        fs::create_dir(dir.join("empty")).unwrap();
+        assert_eq!(check_target(&dir.join("empty"), false), None);
+        let problem = check_target(&dir.join("empty"), true).expect("an existing target is a conflict");
+        assert!(problem.contains("refuses any existing destination"), "{}", problem);
+        assert_eq!(check_target(&dir.join("missing"), true), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn generates_through_a_symlinked_directory() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path();
+        fs::create_dir(dir.join("real")).unwrap();
+        std::os::unix::fs::symlink(dir.join("real"), dir.join("link")).unwrap();
+        let target = dir.join("link").join("app");
+        assert_eq!(dangling_link(&target), None);
+        assert_eq!(check_target(&target, false), None);
+
+        fs::create_dir_all(&target).unwrap();
+        write_file(&target.join("main.py"), "print('hi')\n").unwrap();
+        assert!(dir.join("real").join("app").join("main.py").is_file());
+        assert!(fs::symlink_metadata(dir.join("link")).unwrap().file_type().is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_a_dangling_symlink_on_the_path() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path();
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(dir.join("missing"), &link).unwrap();
+        assert_eq!(dangling_link(&link.join("app")), Some(link.as_path()));
+        let problem = check_target(&link.join("app"), false).expect("a dangling link is a conflict");
+        assert!(problem.contains("symlink to a missing path"), "{}", problem);
+    }
+
+    #[test]
+    fn venv_inside_the_project_is_ignored_relative_to_it() {
+        let base = Path::new("demo");
+        assert_eq!(venv_gitignore_entry(base, &base.join("venv")).as_deref(), Some("/venv/"));
+        assert_eq!(
+            venv_gitignore_entry(base, &base.join(".envs").join("py")).as_deref(),
+            Some("/.envs/py/")
+        );
+        assert_eq!(venv_gitignore_entry(base, &base.join(".").join("venv")).as_deref(), Some("/venv/"));
+    }
+
+    #[test]
+    fn venv_outside_the_project_is_not_ignored() {
+        let base = Path::new("demo");
+        assert_eq!(venv_gitignore_entry(base, Path::new("venvs/demo")), None);
+        assert_eq!(venv_gitignore_entry(base, Path::new("demo-venv")), None);
+        assert_eq!(venv_gitignore_entry(base, base), None);
+    }
+}
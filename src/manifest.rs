@@ -0,0 +1,277 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::download::download;
+use crate::naming;
+use crate::{child_stdout, command_exists, tool_command, write_file, GenContext};
+
+/// A stack defined declaratively by a TOML manifest in the templates directory.
+///
+/// ```toml
+/// name = "express"
+/// about = "Create an Express project"
+/// requires = ["node", "npm"]
+/// gitignore = ["node_modules/"]
+/// post_message = "Run 'cd {project} && npm start' to get going."
+///
+/// [[files]]
+/// path = "index.js"
+/// content = "console.log('hello from {project}');\n"
+///
+/// [[files]]
+/// path = "README.md"
+/// source = "express/README.md"
+///
+/// [[files]]
+/// path = "public/favicon.ico"
+/// url = "https://example.com/favicon.ico"
+/// sha256 = "<hex digest>"
+///
+/// [[commands]]
+/// program = "npm"
+/// args = ["init", "-y"]
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestGenerator {
+    pub name: String,
+    #[serde(default)]
+    pub about: Option<String>,
+    /// Tools that must be on PATH before anything is generated.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+    /// Entries `--gitignore` adds to the project's `.gitignore`.
+    #[serde(default)]
+    pub gitignore: Vec<String>,
+    /// Commands run inside the project directory after the files are written.
+    #[serde(default)]
+    pub commands: Vec<ManifestCommand>,
+    /// Printed once the project is generated, e.g. next steps for the user.
+    /// A `.postmessage` file in `files` works too and is not written out.
+    #[serde(default)]
+    pub post_message: Option<String>,
+    /// Directory the manifest was loaded from; `source` paths are relative to it.
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+/// Subcommand names clap adds itself; a manifest reusing one would clash.
+const RESERVED_NAMES: &[&str] = &["help"];
+
+/// File name that carries a post-generation message instead of project content.
+const POST_MESSAGE_FILE: &str = ".postmessage";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestFile {
+    pub path: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Downloaded and written verbatim, without `{project}` substitution.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Expected SHA-256 of the file at `url`.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Loads every `*.toml` manifest in `dir`, sorted by file name.
+///
+/// A missing directory yields no generators. Manifests that fail to parse are
+/// reported and skipped so one broken file does not disable the whole CLI.
+pub fn load_manifest_generators(ctx: &mut GenContext, dir: &Path) -> Vec<ManifestGenerator> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut generators: Vec<ManifestGenerator> = Vec::new();
+    for path in paths {
+        match load_manifest(&path) {
+            Ok(generator) => {
+                if generators.iter().any(|g| g.name == generator.name) {
+                    warning!(
+                        ctx,
+                        "Skipping template manifest {:?}: stack '{}' is already defined.",
+                        path, generator.name
+                    );
+                    continue;
+                }
+                generators.push(generator);
+            }
+            Err(e) => warning!(ctx, "Skipping template manifest {:?}: {}", path, e),
+        }
+    }
+    generators
+}
+
+fn load_manifest(path: &Path) -> Result<ManifestGenerator> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read file {:?}: {}", path, e))?;
+    let mut generator: ManifestGenerator = toml::from_str(&text)?;
+    if !is_stack_name(&generator.name) {
+        return Err(anyhow!(
+            "the 'name' field '{}' must be lowercase letters, digits, '-' or '_', starting with a letter",
+            generator.name
+        ));
+    }
+    if RESERVED_NAMES.contains(&generator.name.as_str()) {
+        return Err(anyhow!("the 'name' field '{}' is reserved by the CLI", generator.name));
+    }
+    for file in &generator.files {
+        let sources = [file.content.is_some(), file.source.is_some(), file.url.is_some()];
+        if sources.iter().filter(|&&set| set).count() != 1 {
+            return Err(anyhow!(
+                "file '{}' must set exactly one of 'content', 'source' or 'url'",
+                file.path
+            ));
+        }
+        if file.url.is_some() && Path::new(&file.path) == Path::new(POST_MESSAGE_FILE) {
+            return Err(anyhow!("'{}' must use 'content' or 'source'", POST_MESSAGE_FILE));
+        }
+        if let Some(sha256) = &file.sha256 {
+            if file.url.is_none() {
+                return Err(anyhow!("file '{}' sets 'sha256' without 'url'", file.path));
+            }
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(anyhow!("file '{}' has an invalid 'sha256' digest", file.path));
+            }
+        }
+        if !is_contained(Path::new(&file.path)) {
+            return Err(anyhow!("file path '{}' must stay inside the project", file.path));
+        }
+    }
+    generator.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    Ok(generator)
+}
+
+/// Stack names become subcommands, so they are kept to plain identifiers.
+fn is_stack_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// True if `path` is relative and never climbs above its starting directory.
+pub fn is_contained(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+impl ManifestGenerator {
+    /// The clap subcommand registered for this stack.
+    pub fn subcommand(&self) -> clap::Command {
+        let about = self
+            .about
+            .clone()
+            .unwrap_or_else(|| format!("Create a {} project from its template manifest", self.name));
+        clap::Command::new(self.name.clone())
+            .about(about)
+            .arg(clap::Arg::new("project").required(true))
+    }
+
+    pub fn generate(&self, project: &str) -> Result<()> {
+        say!("Creating {} project for: {}", self.name, project);
+        for tool in &self.requires {
+            if !command_exists(tool) {
+                return Err(anyhow!(
+                    "'{}' was not found on your system. It is required for {} projects.",
+                    tool,
+                    self.name
+                ));
+            }
+        }
+
+        let base = Path::new(project);
+        fs::create_dir_all(base)
+            .map_err(|e| anyhow!("Failed to create directory {:?}: {}", base, e))?;
+        let mut post_message = self.post_message.clone();
+        for file in &self.files {
+            let relative = substitute_path(&file.path, project)
+                .ok_or_else(|| anyhow!("file path '{}' must stay inside the project", file.path))?;
+            let path = base.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| anyhow!("Failed to create directory {:?}: {}", parent, e))?;
+            }
+            let content = match (&file.content, &file.source, &file.url) {
+                (Some(content), _, _) => content.clone(),
+                (None, Some(source), _) => {
+                    let source = self.base_dir.join(source);
+                    fs::read_to_string(&source)
+                        .map_err(|e| anyhow!("Failed to read file {:?}: {}", source, e))?
+                }
+                (None, None, Some(url)) => {
+                    download(url, &path, file.sha256.as_deref())?;
+                    continue;
+                }
+                (None, None, None) => unreachable!("validated when the manifest was loaded"),
+            };
+            if Path::new(&file.path) == Path::new(POST_MESSAGE_FILE) {
+                post_message = Some(content);
+                continue;
+            }
+            write_file(&path, &substitute(&content, project))?;
+        }
+
+        for command in &self.commands {
+            let args: Vec<String> = command.args.iter().map(|arg| substitute(arg, project)).collect();
+            let status = tool_command(&command.program)
+                .args(&args)
+                .current_dir(base)
+                .stdout(child_stdout())
+                .status()
+                .map_err(|e| anyhow!("Failed to run '{}': {}", command.program, e))?;
+            if !status.success() {
+                return Err(anyhow!("'{} {}' failed.", command.program, args.join(" ")));
+            }
+        }
+        say!("{} project created successfully!", self.name);
+        if let Some(message) = post_message {
+            say!("{}", substitute(message.trim_end(), project));
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `{project}` with the name as given, and `{project_kebab}` /
+/// `{project_snake}` with package and module names derived from it.
+pub fn substitute(text: &str, project: &str) -> String {
+    let display = directory_name(project);
+    text.replace("{project_kebab}", &naming::to_kebab(display))
+        .replace("{project_snake}", &naming::to_snake(display))
+        .replace("{project}", project)
+}
+
+/// `relative` with its placeholders filled in from the project's directory
+/// name rather than the whole path it was given as, or `None` if the result
+/// would leave the project.
+pub fn substitute_path(relative: &str, project: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(substitute(relative, directory_name(project)));
+    is_contained(&path).then_some(path)
+}
+
+fn directory_name(project: &str) -> &str {
+    Path::new(project)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(project)
+}
@@ -0,0 +1,103 @@
+/// Lowercase ASCII words of `name`, for building package and module names.
+///
+/// Everything that is not an ASCII letter or digit separates words, after
+/// common Latin accents are folded (`é` → `e`, `ß` → `ss`). Other non-ASCII
+/// characters are dropped, so `"My Cool App"` and `"my_cool-app!"` both give
+/// `["my", "cool", "app"]`.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+        } else if let Some(folded) = fold_accent(c) {
+            word.push_str(folded);
+        } else if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn fold_accent(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => "a",
+        'æ' => "ae",
+        'ç' | 'č' | 'ć' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ß' => "ss",
+        'š' | 'ś' => "s",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => "u",
+        'ý' | 'ÿ' => "y",
+        'ž' | 'ź' | 'ż' => "z",
+        'ł' => "l",
+        _ => return None,
+    })
+}
+
+/// URL- and npm-style slug: `"My Cool App"` → `"my-cool-app"`.
+pub fn slugify(name: &str) -> String {
+    words(name).join("-")
+}
+
+/// Name for packages that use dashes, such as crates: same as [`slugify`].
+pub fn to_kebab(name: &str) -> String {
+    slugify(name)
+}
+
+/// Python module or Rust identifier: `"My Cool App"` → `"my_cool_app"`.
+/// A leading digit gets an underscore prefix so the result stays importable.
+pub fn to_snake(name: &str) -> String {
+    let snake = words(name).join("_");
+    if snake.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", snake)
+    } else {
+        snake
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_anything_that_is_not_a_letter_or_digit() {
+        assert_eq!(slugify("My Cool App"), "my-cool-app");
+        assert_eq!(to_snake("my_cool-app!"), "my_cool_app");
+        assert_eq!(to_kebab("  --Mixed__Separators--  "), "mixed-separators");
+    }
+
+    #[test]
+    fn folds_latin_accents() {
+        assert_eq!(slugify("Café Crème"), "cafe-creme");
+        assert_eq!(to_snake("Straße"), "strasse");
+        assert_eq!(to_kebab("Łódź Señor"), "lodz-senor");
+        assert_eq!(to_snake("Æsir Œuvre"), "aesir_oeuvre");
+    }
+
+    #[test]
+    fn folds_uppercase_accents_through_their_lowercase_form() {
+        assert_eq!(slugify("ÉCOLE ÜBER"), "ecole-uber");
+    }
+
+    #[test]
+    fn drops_other_non_ascii_characters() {
+        assert_eq!(slugify("日本 app"), "app");
+        assert_eq!(to_snake("rocket 🚀 launcher"), "rocket_launcher");
+        assert_eq!(slugify("日本"), "");
+    }
+
+    #[test]
+    fn prefixes_a_leading_digit_in_snake_case_only() {
+        assert_eq!(to_snake("3d Engine"), "_3d_engine");
+        assert_eq!(to_kebab("3d Engine"), "3d-engine");
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{anyhow, Result};
+use std::env;
+use std::process::Command;
+
+/// Proxy variables mirrored between their upper- and lowercase spellings.
+const PROXY_VARIABLES: &[(&str, &str)] = &[
+    ("HTTP_PROXY", "http_proxy"),
+    ("HTTPS_PROXY", "https_proxy"),
+    ("ALL_PROXY", "all_proxy"),
+    ("NO_PROXY", "no_proxy"),
+];
+
+/// An agent builder for requests to `url` that goes through the proxy the
+/// standard environment variables name for its scheme (`HTTPS_PROXY` or
+/// `HTTP_PROXY`, then `ALL_PROXY`), unless `NO_PROXY` exempts its host.
+pub fn agent_for(url: &str) -> Result<ureq::AgentBuilder> {
+    // ureq's own lookup ignores both the scheme and NO_PROXY.
+    let builder = ureq::AgentBuilder::new().try_proxy_from_env(false);
+    let Some(proxy) = proxy_for(url) else {
+        return Ok(builder);
+    };
+    let proxy = ureq::Proxy::new(&proxy).map_err(|e| anyhow!("Invalid proxy '{}': {}", proxy, e))?;
+    Ok(builder.proxy(proxy))
+}
+
+/// Makes a child process see the proxy settings under both spellings, since
+/// tools disagree on which one they read.
+pub fn pass_to(command: &mut Command) {
+    for (upper, lower) in PROXY_VARIABLES {
+        match (variable(upper), variable(lower)) {
+            (Some(value), None) => {
+                command.env(lower, value);
+            }
+            (None, Some(value)) => {
+                command.env(upper, value);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn proxy_for(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = host_of(rest);
+    if is_exempt(&host) {
+        return None;
+    }
+    let scheme_variable = if scheme.eq_ignore_ascii_case("https") {
+        "HTTPS_PROXY"
+    } else {
+        "HTTP_PROXY"
+    };
+    lookup(scheme_variable).or_else(|| lookup("ALL_PROXY"))
+}
+
+/// The host of the part of a URL after `scheme://`, lowercased and without
+/// user info, port or brackets.
+fn host_of(rest: &str) -> String {
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = authority.rsplit('@').next().unwrap_or(authority);
+    let host = match host_port.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or(bracketed),
+        None => host_port.split(':').next().unwrap_or(host_port),
+    };
+    host.to_ascii_lowercase()
+}
+
+/// `NO_PROXY` is a comma-separated list of hosts and domain suffixes
+/// (`example.com` also covers `api.example.com`); `*` exempts everything.
+fn is_exempt(host: &str) -> bool {
+    lookup("NO_PROXY").is_some_and(|no_proxy| exempt_by(&no_proxy, host))
+}
+
+fn exempt_by(no_proxy: &str, host: &str) -> bool {
+    no_proxy.split(',').map(str::trim).any(|entry| {
+        // Entries may carry a port, and IPv6 addresses brackets, like URLs do.
+        let entry = if entry.starts_with('[') || entry.matches(':').count() == 1 {
+            host_of(entry)
+        } else {
+            entry.to_ascii_lowercase()
+        };
+        let entry = entry.trim_start_matches('.');
+        entry == "*"
+            || (!entry.is_empty()
+                && (host == entry || host.ends_with(&format!(".{}", entry))))
+    })
+}
+
+/// Reads a proxy variable, preferring the uppercase spelling.
+fn lookup(upper: &str) -> Option<String> {
+    variable(upper).or_else(|| variable(&upper.to_ascii_lowercase()))
+}
+
+fn variable(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_the_host() {
+        for (rest, host) in [
+            ("example.com/path", "example.com"),
+            ("Example.COM:8443/", "example.com"),
+            ("user:secret@example.com:80?q=1", "example.com"),
+            ("[::1]:8080/", "::1"),
+            ("[FE80::1]", "fe80::1"),
+            ("example.com#top", "example.com"),
+        ] {
+            assert_eq!(host_of(rest), host, "{}", rest);
+        }
+    }
+
+    #[test]
+    fn exempts_hosts_listed_in_no_proxy() {
+        for (no_proxy, host, exempt) in [
+            ("example.com", "example.com", true),
+            ("example.com", "api.example.com", true),
+            (".example.com", "api.example.com", true),
+            ("example.com", "badexample.com", false),
+            ("localhost, example.com:8080", "example.com", true),
+            ("*", "crates.io", true),
+            ("", "crates.io", false),
+            ("[::1]:8080", "::1", true),
+            ("::1", "::1", true),
+            ("EXAMPLE.com", "example.com", true),
+        ] {
+            assert_eq!(exempt_by(no_proxy, host), exempt, "NO_PROXY={:?} host={}", no_proxy, host);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Built-in file templates written by the generators.
+
+pub const SYMFONY_INDEX_PHP: &str = "<?php\n// Symfony front controller placeholder\n";
+
+pub const FLASK_APP_PY: &str = r#"from flask import Flask
+
+app = Flask(__name__)
+
+@app.route('/')
+def hello():
+    return "Here we go again!"
+
+if __name__ == '__main__':
+    app.run(debug=True, port={port})
+"#;
+
+pub const DJANGO_MANAGE_PY: &str = r#"#!/usr/bin/env python
+import os
+import sys
+
+if __name__ == '__main__':
+    os.environ.setdefault('DJANGO_SETTINGS_MODULE', 'project.settings')
+    try:
+        from django.core.management import execute_from_command_line
+    except ImportError as exc:
+        raise ImportError("Couldn't import Django.") from exc
+    execute_from_command_line(sys.argv)
+"#;
+
+pub const DJANGO_SETTINGS_PY: &str = r#"import os
+from pathlib import Path
+
+# The project's .env, kept out of git, fills in what the environment does not set.
+_env_file = Path(__file__).resolve().parent.parent / '.env'
+if _env_file.is_file():
+    for _line in _env_file.read_text().splitlines():
+        _key, _sep, _value = _line.partition('=')
+        if _sep and not _key.lstrip().startswith('#'):
+            os.environ.setdefault(_key.strip(), _value.strip())
+
+SECRET_KEY = os.environ['DJANGO_SECRET_KEY']
+DEBUG = os.environ.get('DJANGO_DEBUG', '1') == '1'
+ALLOWED_HOSTS = []
+INSTALLED_APPS = [
+    'django.contrib.admin',
+    'django.contrib.auth',
+    'django.contrib.contenttypes',
+    'django.contrib.sessions',
+    'django.contrib.messages',
+    'django.contrib.staticfiles',
+    'app',
+]
+MIDDLEWARE = [
+    'django.middleware.security.SecurityMiddleware',
+    'django.contrib.sessions.middleware.SessionMiddleware',
+    'django.middleware.common.CommonMiddleware',
+]
+ROOT_URLCONF = 'project.urls'
+"#;
+
+/// Root URLconf of the fallback scaffold with `--drf`.
+pub const DJANGO_DRF_URLS_PY: &str = r#"from django.urls import include, path
+
+urlpatterns = [
+    path('api/', include('api.urls')),
+]
+"#;
+
+/// Appended to the URLconf `django-admin startproject` writes, for `--drf`.
+pub const DJANGO_DRF_URLS_APPENDIX: &str = r#"
+from django.urls import include  # noqa: E402
+
+urlpatterns += [
+    path('api/', include('api.urls')),
+]
+"#;
+
+pub const DRF_SERIALIZERS_PY: &str = r#"from django.contrib.auth.models import User
+from rest_framework import serializers
+
+
+class UserSerializer(serializers.HyperlinkedModelSerializer):
+    class Meta:
+        model = User
+        fields = ['url', 'username', 'email']
+"#;
+
+pub const DRF_VIEWS_PY: &str = r#"from django.contrib.auth.models import User
+from rest_framework import permissions, viewsets
+
+from .serializers import UserSerializer
+
+
+class UserViewSet(viewsets.ModelViewSet):
+    queryset = User.objects.all().order_by('-date_joined')
+    serializer_class = UserSerializer
+    permission_classes = [permissions.IsAuthenticated]
+"#;
+
+pub const DRF_API_URLS_PY: &str = r#"from django.urls import include, path
+from rest_framework import routers
+
+from .views import UserViewSet
+
+router = routers.DefaultRouter()
+router.register(r'users', UserViewSet)
+
+urlpatterns = [
+    path('', include(router.urls)),
+]
+"#;
+
+/// Hooks every `--pre-commit` config starts with.
+pub const PRE_COMMIT_BASE: &str = r#"  - repo: https://github.com/pre-commit/pre-commit-hooks
+    rev: v4.6.0
+    hooks:
+      - id: trailing-whitespace
+      - id: end-of-file-fixer
+      - id: check-yaml
+"#;
+
+pub const PRE_COMMIT_PYTHON: &str = r#"  - repo: https://github.com/astral-sh/ruff-pre-commit
+    rev: v0.6.9
+    hooks:
+      - id: ruff
+      - id: ruff-format
+"#;
+
+pub const PRE_COMMIT_RUST: &str = r#"  - repo: local
+    hooks:
+      - id: rustfmt
+        name: rustfmt
+        entry: cargo fmt --all --
+        language: system
+        types: [rust]
+      - id: clippy
+        name: clippy
+        entry: cargo clippy --all-targets -- -D warnings
+        language: system
+        types: [rust]
+        pass_filenames: false
+"#;
+
+pub const PRE_COMMIT_PHP: &str = r#"  - repo: local
+    hooks:
+      - id: php-cs-fixer
+        name: php-cs-fixer
+        entry: vendor/bin/php-cs-fixer fix
+        language: system
+        types: [php]
+"#;
+
+pub const WORKER_INDEX_JS: &str = r#"export default {
+  async fetch(request, env, ctx) {
+    return new Response('Hello from your Worker!');
+  },
+};
+"#;
+
+/// `{name}` is replaced by the Worker's package-safe name.
+pub const WRANGLER_TOML: &str = r#"name = "{name}"
+main = "src/index.js"
+compatibility_date = "2024-09-23"
+
+[dev]
+port = {port}
+"#;
+
+pub const PRE_COMMIT_JS: &str = r#"  - repo: https://github.com/pre-commit/mirrors-prettier
+    rev: v3.1.0
+    hooks:
+      - id: prettier
+"#;
+
+pub const RUST_MAIN_RS: &str = r#"fn main() {
+    println!("Hello, world!");
+}
+"#;
+
+pub const PROC_MACRO_LIB_RS: &str = r##"use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives a `hello()` associated function returning the type's name.
+#[proc_macro_derive(Hello)]
+pub fn derive_hello(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn hello() -> &'static str {
+                stringify!(#name)
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
+"##;
+
+pub const YEW_MAIN_RS: &str = r#"use yew::prelude::*;
+
+#[function_component(App)]
+fn app() -> Html {
+    let counter = use_state(|| 0);
+    let onclick = {
+        let counter = counter.clone();
+        Callback::from(move |_| counter.set(*counter + 1))
+    };
+    html! {
+        <main>
+            <h1>{ "Hello from Yew!" }</h1>
+            <button {onclick}>{ "+1" }</button>
+            <p>{ *counter }</p>
+        </main>
+    }
+}
+
+fn main() {
+    yew::Renderer::<App>::new().render();
+}
+"#;
+
+pub const YEW_INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <title>Yew App</title>
+  </head>
+  <body></body>
+</html>
+"#;
+
+pub const TRUNK_TOML: &str = r#"[build]
+target = "index.html"
+dist = "dist"
+
+[serve]
+port = {port}
+"#;
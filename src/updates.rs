@@ -0,0 +1,100 @@
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+use crate::{proxy, GenContext};
+
+/// A dependency version a template writes into a generated project.
+pub struct PinnedDependency<'a> {
+    pub name: &'a str,
+    /// Cargo-style requirement, e.g. `"2"` or `"0.22"`.
+    pub version: &'a str,
+}
+
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Asks crates.io for the newest release of every pin and warns about pins
+/// whose requirement no longer admits it. Network failures are reported but
+/// never fail the run.
+pub fn check_updates(ctx: &mut GenContext, pins: &[PinnedDependency]) {
+    if pins.is_empty() {
+        say!("No pinned dependency versions to check.");
+        return;
+    }
+    say!("Checking pinned dependency versions against crates.io...");
+    for pin in pins {
+        match latest_crate_version(pin.name) {
+            Ok(latest) if is_behind(pin.version, &latest) => warning!(
+                ctx,
+                "{} is pinned to \"{}\" but the latest release is {}.",
+                pin.name,
+                pin.version,
+                latest
+            ),
+            Ok(_) => say!("{} \"{}\" is up to date.", pin.name, pin.version),
+            Err(e) => say!("Could not check {}: {}", pin.name, e),
+        }
+    }
+}
+
+fn latest_crate_version(name: &str) -> Result<String> {
+    let url = format!("https://crates.io/api/v1/crates/{}", name);
+    let body = proxy::agent_for(&url)?
+        .build()
+        .get(&url)
+        // crates.io rejects requests without a descriptive user agent.
+        .set("User-Agent", concat!("project_generator_cli/", env!("CARGO_PKG_VERSION")))
+        .timeout(REGISTRY_TIMEOUT)
+        .call()?
+        .into_string()?;
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    json["crate"]["max_stable_version"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("crates.io returned no stable version"))
+}
+
+/// True if `latest` is newer than `requirement` and outside its caret range.
+fn is_behind(requirement: &str, latest: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .take(3)
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    let pinned = parse(requirement);
+    let latest = parse(latest);
+    if pinned.is_empty() || latest.is_empty() {
+        return false;
+    }
+    // Caret semantics: everything up to the first non-zero component is fixed.
+    let fixed = pinned
+        .iter()
+        .position(|&part| part != 0)
+        .unwrap_or(pinned.len() - 1);
+    let compatible = (0..=fixed).all(|i| latest.get(i).copied().unwrap_or(0) == pinned[i]);
+    !compatible && latest > pinned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_against_the_caret_range() {
+        for (requirement, latest, behind) in [
+            ("1", "1.9.3", false),
+            ("1", "2.0.0", true),
+            ("1.2", "1.3.0", false),
+            ("0.22", "0.22.9", false),
+            ("0.22", "0.23.0", true),
+            ("0.0.3", "0.0.4", true),
+            ("0.0.3", "0.0.3", false),
+            ("2", "1.9.0", false),
+            ("0.8", "0.9.0-rc.1", true),
+            ("*", "1.0.0", false),
+        ] {
+            assert_eq!(is_behind(requirement, latest), behind, "{} vs {}", requirement, latest);
+        }
+    }
+}